@@ -0,0 +1,79 @@
+//! Compare matching typical `wrd mw` and `wrd nw` queries against the Moby
+//! dictionary with its index, and against a copy of its words without one.
+
+use std::hint::black_box;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use wrd_lib::{
+	Dictionary, Notwordle, WithinMode, get_dictionary, get_dictionary_index, match_words,
+};
+
+/// name, pattern, include, exclude, within
+const MW_QUERIES: [(&str, &str, &str, &str, &str); 4] = [
+	("five letters", "* r * nt !sy", "", "", ""),
+	("include and exclude", "**", "qu", "aeo", ""),
+	("rack", "* * * **", "d", "", "trubador"),
+	("ending", "*{1,3} \"ing\"", "", "", ""),
+];
+
+fn bench_match_words(c: &mut Criterion) {
+	let dict = get_dictionary(&Dictionary::Moby).unwrap_or_default();
+	let copy: Vec<&str> = dict.to_vec();
+
+	// build the index before measuring
+	let _ = get_dictionary_index(&Dictionary::Moby);
+
+	let mut group = c.benchmark_group("mw");
+
+	for (name, pattern, include, exclude, within) in MW_QUERIES {
+		group.bench_function(format!("{name} indexed"), |b| {
+			b.iter(|| {
+				match_words(
+					black_box(pattern),
+					include,
+					exclude,
+					within,
+					WithinMode::Rack,
+					Some(dict),
+				)
+			});
+		});
+		group.bench_function(format!("{name} scanned"), |b| {
+			b.iter(|| {
+				match_words(
+					black_box(pattern),
+					include,
+					exclude,
+					within,
+					WithinMode::Rack,
+					Some(&copy),
+				)
+			});
+		});
+	}
+
+	group.finish();
+}
+
+fn bench_notwordle(c: &mut Criterion) {
+	let dict = get_dictionary(&Dictionary::Moby).unwrap_or_default();
+	let copy: Vec<&str> = dict.to_vec();
+	let mut notwordle = Notwordle::default();
+
+	let _ = get_dictionary_index(&Dictionary::Moby);
+	let _ = notwordle.register_guess_result("crane:..y.g");
+	let _ = notwordle.register_guess_result("pilot:.y...");
+
+	let mut group = c.benchmark_group("nw");
+
+	group.bench_function("two guesses indexed", |b| {
+		b.iter(|| notwordle.refine(Some(black_box(dict))));
+	});
+	group.bench_function("two guesses scanned", |b| {
+		b.iter(|| notwordle.refine(Some(black_box(&copy))));
+	});
+	group.finish();
+}
+
+criterion_group!(benches, bench_match_words, bench_notwordle);
+criterion_main!(benches);
@@ -0,0 +1,350 @@
+use std::collections::BTreeMap;
+
+use crate::feedback::score_guess;
+use crate::notwordle::{GuessResultToken, Notwordle, NotwordleError};
+use crate::parallel;
+use crate::solver::rank_guesses;
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum BenchError {
+	#[error("no {0} letter words to play")]
+	NoWords(usize),
+	#[error("opening {opening} must have {expected} letters")]
+	InvalidOpening { opening: String, expected: usize },
+	#[error("opening {0} must be letters a-z")]
+	OpeningNotLetters(String),
+	#[error("opening {0} is not in the dictionary")]
+	OpeningNotInDictionary(String),
+	#[error("{answer} was ruled out after guessing {}", guesses.join(", "))]
+	AnswerEliminated {
+		answer: &'static str,
+		guesses: Vec<String>,
+	},
+	#[error(transparent)]
+	Notwordle(#[from] NotwordleError),
+}
+
+/// How the solver picks its next guess
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Strategy {
+	/// the most informative word that could still be the answer
+	#[default]
+	Entropy,
+	/// the most informative word of the right length, even if already ruled out
+	EntropyAny,
+	/// the first word that could still be the answer, in dictionary order
+	First,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BenchOptions {
+	pub word_length: usize,
+	/// first guess for every game, picked by `strategy` if not set
+	pub opening: Option<String>,
+	pub strategy: Strategy,
+	/// games needing more guesses than this count as failures
+	pub max_guesses: usize,
+}
+
+impl Default for BenchOptions {
+	fn default() -> Self {
+		Self {
+			word_length: 5,
+			opening: None,
+			strategy: Strategy::default(),
+			max_guesses: 6,
+		}
+	}
+}
+
+/// Guesses needed to solve each answer, in dictionary order
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BenchReport {
+	pub opening: String,
+	pub games: Vec<(&'static str, usize)>,
+	pub max_guesses: usize,
+}
+
+impl BenchReport {
+	#[must_use]
+	pub fn mean(&self) -> f64 {
+		let total: usize = self.games.iter().map(|(_, guesses)| guesses).sum();
+
+		to_f64(total) / to_f64(self.games.len()).max(1.0)
+	}
+
+	#[must_use]
+	pub fn median(&self) -> f64 {
+		let mut guesses: Vec<usize> = self.games.iter().map(|(_, guesses)| *guesses).collect();
+
+		guesses.sort_unstable();
+
+		let mid = guesses.len() / 2;
+
+		match (guesses.get(mid.wrapping_sub(1)), guesses.get(mid)) {
+			(Some(a), Some(b)) if guesses.len().is_multiple_of(2) => {
+				to_f64(a.saturating_add(*b)) / 2.0
+			}
+			(_, Some(b)) => to_f64(*b),
+			_ => 0.0,
+		}
+	}
+
+	#[must_use]
+	pub fn max(&self) -> usize {
+		self.games
+			.iter()
+			.map(|(_, guesses)| *guesses)
+			.max()
+			.unwrap_or_default()
+	}
+
+	/// Answers that took more than `max_guesses`, in dictionary order
+	#[must_use]
+	pub fn failures(&self) -> Vec<&'static str> {
+		self.games
+			.iter()
+			.filter(|(_, guesses)| *guesses > self.max_guesses)
+			.map(|(answer, _)| *answer)
+			.collect()
+	}
+
+	#[must_use]
+	pub fn failure_rate(&self) -> f64 {
+		to_f64(self.failures().len()) / to_f64(self.games.len()).max(1.0)
+	}
+
+	/// Number of games solved in each number of guesses
+	#[must_use]
+	pub fn histogram(&self) -> BTreeMap<usize, usize> {
+		self.games
+			.iter()
+			.fold(BTreeMap::new(), |mut histogram, (_, guesses)| {
+				let count: &mut usize = histogram.entry(*guesses).or_default();
+
+				*count = count.saturating_add(1);
+				histogram
+			})
+	}
+}
+
+/// Solve every word of the configured length in `words`, using `Notwordle` to
+/// narrow down the answer after each guess.
+///
+/// Answers are played in parallel with the `parallel` feature, results are
+/// the same on every run.
+///
+/// # Errors
+/// Errors if there are no words to play, or the opening has the wrong length,
+/// is not letters a-z or is not in `words`.
+/// Errors if refining ever rules out the answer, which would be a bug.
+pub fn bench(words: &[&'static str], options: &BenchOptions) -> Result<BenchReport, BenchError> {
+	let answers: Vec<&'static str> = words
+		.iter()
+		.filter(|word| {
+			word.len() == options.word_length && word.bytes().all(|b| b.is_ascii_lowercase())
+		})
+		.copied()
+		.collect();
+
+	if answers.is_empty() {
+		return Err(BenchError::NoWords(options.word_length));
+	}
+
+	let opening = match &options.opening {
+		Some(opening) if opening.chars().count() != options.word_length => {
+			return Err(BenchError::InvalidOpening {
+				opening: opening.clone(),
+				expected: options.word_length,
+			});
+		}
+		Some(opening) if !opening.bytes().all(|b| b.is_ascii_lowercase()) => {
+			return Err(BenchError::OpeningNotLetters(opening.clone()));
+		}
+		Some(opening) if !answers.contains(&opening.as_str()) => {
+			return Err(BenchError::OpeningNotInDictionary(opening.clone()));
+		}
+		Some(opening) => opening.clone(),
+		None => next_guess(options.strategy, &answers, &answers).to_string(),
+	};
+	let games = parallel::map(&answers, |answer| {
+		play(answer, &opening, options.strategy, &answers).map(|guesses| (*answer, guesses))
+	})
+	.into_iter()
+	.collect::<Result<Vec<_>, BenchError>>()?;
+
+	Ok(BenchReport {
+		opening,
+		games,
+		max_guesses: options.max_guesses,
+	})
+}
+
+/// Number of guesses `strategy` needs to find `answer`
+fn play(
+	answer: &'static str,
+	opening: &str,
+	strategy: Strategy,
+	words: &[&'static str],
+) -> Result<usize, BenchError> {
+	let mut notwordle = Notwordle::default();
+	let mut remaining = words.to_vec();
+	let mut guesses = vec![opening.to_string()];
+
+	loop {
+		let guess = guesses.last().map(String::as_str).unwrap_or_default();
+		let result = score_guess(guess, answer)?;
+
+		if result
+			.iter()
+			.all(|token| matches!(token, GuessResultToken::Right(_)))
+		{
+			return Ok(guesses.len());
+		}
+
+		notwordle.register_guess_tokens(result)?;
+		// constraints only ever narrow, so refine what is left rather than all words
+		remaining = notwordle
+			.remaining(Some(&remaining))
+			.map_err(NotwordleError::from)?;
+
+		if !remaining.contains(&answer) {
+			return Err(BenchError::AnswerEliminated { answer, guesses });
+		}
+
+		guesses.push(next_guess(strategy, words, &remaining).to_string());
+	}
+}
+
+fn next_guess(
+	strategy: Strategy,
+	words: &[&'static str],
+	remaining: &[&'static str],
+) -> &'static str {
+	let ranked = match strategy {
+		Strategy::Entropy => rank_guesses(remaining, remaining, 1),
+		Strategy::EntropyAny => rank_guesses(words, remaining, 1),
+		Strategy::First => vec![],
+	};
+
+	ranked
+		.first()
+		.map(|suggestion| suggestion.word)
+		.or_else(|| remaining.first().copied())
+		.unwrap_or_default()
+}
+
+fn to_f64(value: usize) -> f64 {
+	f64::from(u32::try_from(value).unwrap_or(u32::MAX))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+	use super::*;
+	use crate::data::{Dictionary, get_dictionary};
+
+	/// A spread of five letter words, with "crane" to open with
+	fn some_five_letter_words() -> Vec<&'static str> {
+		let mut words: Vec<&'static str> = get_dictionary(&Dictionary::Gwicks)
+			.unwrap()
+			.iter()
+			.filter(|w| w.len() == 5 && w.bytes().all(|b| b.is_ascii_lowercase()))
+			.step_by(23)
+			.copied()
+			.collect();
+
+		if !words.contains(&"crane") {
+			words.push("crane");
+		}
+
+		words
+	}
+
+	#[test]
+	fn should_solve_every_answer() {
+		let words = some_five_letter_words();
+
+		for strategy in [Strategy::Entropy, Strategy::EntropyAny, Strategy::First] {
+			let options = BenchOptions {
+				opening: Some("crane".to_string()),
+				strategy,
+				..BenchOptions::default()
+			};
+			let report = bench(&words, &options).unwrap();
+
+			assert_eq!(report.games.len(), words.len());
+			assert_eq!(
+				report
+					.games
+					.iter()
+					.map(|(answer, _)| *answer)
+					.collect::<Vec<_>>(),
+				words
+			);
+			assert_eq!(report, bench(&words, &options).unwrap());
+		}
+	}
+
+	#[test]
+	fn should_pick_an_opening() {
+		let words = ["batch", "hatch", "latch", "match", "patch", "watch"];
+		let report = bench(&words, &BenchOptions::default()).unwrap();
+
+		assert_eq!(report.opening, "batch");
+		assert_eq!(report.games.first(), Some(&("batch", 1)));
+	}
+
+	#[test]
+	fn should_summarize_games() {
+		let report = BenchReport {
+			opening: "crane".to_string(),
+			games: vec![("a", 3), ("b", 4), ("c", 3), ("d", 8)],
+			max_guesses: 6,
+		};
+
+		assert!((report.mean() - 4.5).abs() < 1e-9);
+		assert!((report.median() - 3.5).abs() < 1e-9);
+		assert_eq!(report.max(), 8);
+		assert_eq!(report.failures(), vec!["d"]);
+		assert!((report.failure_rate() - 0.25).abs() < 1e-9);
+		assert_eq!(
+			report.histogram().into_iter().collect::<Vec<_>>(),
+			vec![(3, 2), (4, 1), (8, 1)]
+		);
+	}
+
+	#[test]
+	fn should_error_on_invalid_options() {
+		let options = BenchOptions {
+			opening: Some("cranes".to_string()),
+			..BenchOptions::default()
+		};
+
+		assert_eq!(
+			bench(&["crane"], &options).unwrap_err(),
+			BenchError::InvalidOpening {
+				opening: "cranes".to_string(),
+				expected: 5
+			}
+		);
+		assert_eq!(
+			bench(&["cranes"], &BenchOptions::default()).unwrap_err(),
+			BenchError::NoWords(5)
+		);
+
+		let opening = |opening: &str| BenchOptions {
+			opening: Some(opening.to_string()),
+			..BenchOptions::default()
+		};
+
+		assert_eq!(
+			bench(&["crane"], &opening("12345")).unwrap_err(),
+			BenchError::OpeningNotLetters("12345".to_string())
+		);
+		assert_eq!(
+			bench(&["crane"], &opening("slate")).unwrap_err(),
+			BenchError::OpeningNotInDictionary("slate".to_string())
+		);
+	}
+}
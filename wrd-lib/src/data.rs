@@ -1,96 +1,823 @@
 use std::borrow::Cow;
 use std::collections::HashMap;
-use std::str;
-use std::sync::LazyLock;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, Mutex, PoisonError, RwLock};
+use std::{fs, io, str};
 
 use rust_embed::Embed;
 
+use crate::isomorph::IsomorphIndex;
 use crate::util::non_empty_str;
 
 #[derive(Embed)]
 #[folder = "data"]
 struct Assets;
 
-// is this is a good idea? not sure if this is a good idea. feels very hacky.
-// TODO: just paste paste dictionaries in as rust files?
-static DICT_DATA: LazyLock<HashMap<&'static str, Cow<'static, [u8]>>> =
-	LazyLock::new(load_dict_data);
-static DICTIONARIES: LazyLock<HashMap<&'static str, Vec<&'static str>>> =
-	LazyLock::new(parse_dict_data);
+static REGISTRY: LazyLock<DictionaryRegistry> = LazyLock::new(DictionaryRegistry::default);
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(rename_all = "lowercase")
+)]
 pub enum Dictionary {
 	Moby,
 	Gwicks,
+	/// plain text word list(s) on disk, one word per line. `path` may point at
+	/// a single file or a directory of files
+	Custom {
+		name: String,
+		path: PathBuf,
+	},
 }
 
 impl Dictionary {
+	/// Create a file backed dictionary, named after the file (or directory) stem.
 	#[must_use]
-	pub const fn name(&self) -> &'static str {
-		match *self {
+	pub fn custom(path: impl Into<PathBuf>) -> Self {
+		let path = path.into();
+		let name = path
+			.file_stem()
+			.map_or_else(|| path.to_string_lossy(), |stem| stem.to_string_lossy())
+			.into_owned();
+
+		Self::Custom { name, path }
+	}
+
+	#[must_use]
+	pub fn name(&self) -> &str {
+		match self {
 			Self::Moby => "moby",
 			Self::Gwicks => "gwicks",
+			Self::Custom { name, .. } => name,
 		}
 	}
 
-	const fn asset(&self) -> &'static str {
+	const fn asset(&self) -> Option<&'static str> {
 		match *self {
-			Self::Moby => "words-moby.txt",
-			Self::Gwicks => "words-gwicks-usa2.txt",
+			Self::Moby => Some("words-moby.txt"),
+			Self::Gwicks => Some("words-gwicks-usa2.txt"),
+			Self::Custom { .. } => None,
 		}
 	}
+
+	/// How often `word` is used, from the frequencies set for this dictionary
+	/// in the global registry. `None` when the word or the frequencies are
+	/// unknown.
+	#[must_use]
+	pub fn frequency(&self, word: &str) -> Option<u64> {
+		DictionaryRegistry::global()
+			.frequencies(self)
+			.and_then(|frequencies| frequencies.get(word))
+	}
 }
 
-#[derive(Debug, thiserror::Error)]
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
 pub enum DataError {
 	#[error("dictionary {0} not found")]
 	DictionaryNotFound(String),
+	#[error("could not read dictionary {path}: {cause}")]
+	ReadFailed { path: String, cause: String },
+	#[error("dictionary {path} is not valid utf-8: {cause}")]
+	InvalidUtf8 { path: String, cause: String },
+	#[error("dictionary {0} contains no words")]
+	EmptyDictionary(String),
+	#[error("a different dictionary named {0} is already registered")]
+	DuplicateDictionary(String),
+	#[error("invalid word frequency in {path} on line {line}: {cause}")]
+	InvalidFrequency {
+		path: String,
+		line: usize,
+		cause: String,
+	},
+	#[error("no word frequencies loaded for dictionary {0}")]
+	NoFrequencies(String),
+}
+
+/// Known dictionaries, each loaded the first time its words are requested.
+///
+/// Loaded word lists are kept for the lifetime of the process so they can be
+/// handed out as `'static` slices, regardless of where they were loaded from.
+#[derive(Debug)]
+pub struct DictionaryRegistry {
+	dictionaries: RwLock<Vec<Dictionary>>,
+	loaded: Mutex<HashMap<Dictionary, &'static [&'static str]>>,
+	indexes: Mutex<HashMap<Dictionary, &'static DictionaryIndex>>,
+	isomorph_indexes: Mutex<HashMap<Dictionary, &'static IsomorphIndex>>,
+	frequencies: RwLock<HashMap<Dictionary, Arc<WordFrequencies>>>,
+}
+
+impl Default for DictionaryRegistry {
+	fn default() -> Self {
+		Self {
+			dictionaries: RwLock::new(vec![Dictionary::Moby, Dictionary::Gwicks]),
+			loaded: Mutex::new(HashMap::new()),
+			indexes: Mutex::new(HashMap::new()),
+			isomorph_indexes: Mutex::new(HashMap::new()),
+			frequencies: RwLock::new(HashMap::new()),
+		}
+	}
+}
+
+impl DictionaryRegistry {
+	/// The process wide registry used by `get_dictionary`.
+	#[must_use]
+	pub fn global() -> &'static Self {
+		&REGISTRY
+	}
+
+	/// Add a dictionary to the registry. Registering the same dictionary twice
+	/// is a no-op. Words are not loaded until first requested.
+	///
+	/// # Errors
+	/// Errors if a different dictionary is already registered with the same name.
+	pub fn register(&self, dict: Dictionary) -> Result<(), DataError> {
+		let mut dictionaries = self
+			.dictionaries
+			.write()
+			.unwrap_or_else(PoisonError::into_inner);
+
+		let result = match dictionaries.iter().find(|d| d.name() == dict.name()) {
+			Some(existing) if *existing == dict => Ok(()),
+			Some(_) => Err(DataError::DuplicateDictionary(dict.name().to_string())),
+			None => {
+				dictionaries.push(dict);
+				Ok(())
+			}
+		};
+
+		drop(dictionaries);
+
+		result
+	}
+
+	/// Remove a dictionary from the registry, e.g. one whose words could not
+	/// be loaded. Removing a dictionary that is not registered is a no-op.
+	pub fn unregister(&self, dict: &Dictionary) {
+		self.dictionaries
+			.write()
+			.unwrap_or_else(PoisonError::into_inner)
+			.retain(|d| d != dict);
+	}
+
+	/// All registered dictionaries, in registration order.
+	#[must_use]
+	pub fn dictionaries(&self) -> Vec<Dictionary> {
+		self.dictionaries
+			.read()
+			.unwrap_or_else(PoisonError::into_inner)
+			.clone()
+	}
+
+	/// Look up a registered dictionary by name.
+	#[must_use]
+	pub fn find(&self, name: &str) -> Option<Dictionary> {
+		self.dictionaries
+			.read()
+			.unwrap_or_else(PoisonError::into_inner)
+			.iter()
+			.find(|d| d.name() == name)
+			.cloned()
+	}
+
+	/// Get the words in a dictionary, loading them on first use.
+	///
+	/// # Errors
+	/// Errors if the dictionary data can not be found or read, is not valid
+	/// utf-8 or contains no words.
+	pub fn get(&self, dict: &Dictionary) -> Result<&'static [&'static str], DataError> {
+		if let Some(words) = self
+			.loaded
+			.lock()
+			.unwrap_or_else(PoisonError::into_inner)
+			.get(dict)
+		{
+			return Ok(words);
+		}
+
+		// load without the lock, so lookups of other dictionaries don't wait on
+		// the disk. if another thread loaded it meanwhile, its words are kept so
+		// every caller gets the same slice
+		let words = load_dictionary(dict)?;
+		let mut loaded = self.loaded.lock().unwrap_or_else(PoisonError::into_inner);
+		let words = *loaded.entry(dict.clone()).or_insert(words);
+
+		drop(loaded);
+
+		Ok(words)
+	}
+
+	/// Get the index of a dictionary's words, building it on first use.
+	///
+	/// # Errors
+	/// Propagates errors from `DictionaryRegistry::get`.
+	pub fn index(&self, dict: &Dictionary) -> Result<&'static DictionaryIndex, DataError> {
+		let words = self.get(dict)?;
+		let mut indexes = self.indexes.lock().unwrap_or_else(PoisonError::into_inner);
+
+		if let Some(index) = indexes.get(dict) {
+			return Ok(index);
+		}
+
+		let index: &'static DictionaryIndex = Box::leak(Box::new(DictionaryIndex::new(words)));
+
+		indexes.insert(dict.clone(), index);
+		drop(indexes);
+
+		Ok(index)
+	}
+
+	/// Get a dictionary's words grouped by isomorph key, building the index
+	/// on first use.
+	///
+	/// # Errors
+	/// Propagates errors from `DictionaryRegistry::get`.
+	pub fn isomorph_index(&self, dict: &Dictionary) -> Result<&'static IsomorphIndex, DataError> {
+		let words = self.get(dict)?;
+		let mut indexes = self
+			.isomorph_indexes
+			.lock()
+			.unwrap_or_else(PoisonError::into_inner);
+
+		if let Some(index) = indexes.get(dict) {
+			return Ok(index);
+		}
+
+		let index: &'static IsomorphIndex = Box::leak(Box::new(IsomorphIndex::new(words)));
+
+		indexes.insert(dict.clone(), index);
+		drop(indexes);
+
+		Ok(index)
+	}
+
+	/// The index of the loaded dictionary `words` came from, if they are a
+	/// loaded dictionary's words
+	pub(crate) fn index_of(&self, words: &[&str]) -> Option<&'static DictionaryIndex> {
+		self.index(&self.loaded_from(words)?).ok()
+	}
+
+	/// The isomorph index of the loaded dictionary `words` came from, if they
+	/// are a loaded dictionary's words
+	pub(crate) fn isomorph_index_of(&self, words: &[&str]) -> Option<&'static IsomorphIndex> {
+		self.isomorph_index(&self.loaded_from(words)?).ok()
+	}
+
+	/// The dictionary `words` were loaded for, if they are the very slice
+	/// handed out by `get`
+	fn loaded_from(&self, words: &[&str]) -> Option<Dictionary> {
+		self.loaded
+			.lock()
+			.unwrap_or_else(PoisonError::into_inner)
+			.iter()
+			.find(|(_, loaded)| {
+				loaded.as_ptr().addr() == words.as_ptr().addr() && loaded.len() == words.len()
+			})
+			.map(|(dict, _)| dict.clone())
+	}
+
+	/// Read word frequencies for a dictionary from a file, see
+	/// `WordFrequencies::read`, replacing any set before.
+	///
+	/// # Errors
+	/// Propagates errors from `WordFrequencies::read`.
+	pub fn load_frequencies(&self, dict: &Dictionary, path: &Path) -> Result<(), DataError> {
+		self.set_frequencies(dict, WordFrequencies::read(path)?);
+
+		Ok(())
+	}
+
+	/// Use `frequencies` for a dictionary's words, replacing any set before.
+	pub fn set_frequencies(&self, dict: &Dictionary, frequencies: WordFrequencies) {
+		self.frequencies
+			.write()
+			.unwrap_or_else(PoisonError::into_inner)
+			.insert(dict.clone(), Arc::new(frequencies));
+	}
+
+	/// The word frequencies set for a dictionary, if any.
+	#[must_use]
+	pub fn frequencies(&self, dict: &Dictionary) -> Option<Arc<WordFrequencies>> {
+		self.frequencies
+			.read()
+			.unwrap_or_else(PoisonError::into_inner)
+			.get(dict)
+			.cloned()
+	}
+}
+
+/// How often words are used, higher is more common
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WordFrequencies {
+	counts: HashMap<String, u64>,
+}
+
+impl WordFrequencies {
+	/// Read a frequency list: one word and its count per line, separated by
+	/// spaces, a tab or a comma, e.g. `crane 1204`. Words are lowercased, and
+	/// the counts of words listed more than once are added up.
+	///
+	/// # Errors
+	/// Errors if the file can not be read, is not valid utf-8 or has a line
+	/// without a word and a count.
+	pub fn read(path: &Path) -> Result<Self, DataError> {
+		let bytes = fs::read(path).map_err(|e| read_error(path, &e))?;
+		let text = String::from_utf8(bytes).map_err(|e| DataError::InvalidUtf8 {
+			path: path.display().to_string(),
+			cause: e.utf8_error().to_string(),
+		})?;
+
+		Self::parse(&text).map_err(|(line, cause)| DataError::InvalidFrequency {
+			path: path.display().to_string(),
+			line,
+			cause,
+		})
+	}
+
+	/// Parse the contents of a frequency list, see `WordFrequencies::read`.
+	/// Errors with the line number and what is wrong with the line.
+	fn parse(text: &str) -> Result<Self, (usize, String)> {
+		let mut counts: HashMap<String, u64> = HashMap::new();
+
+		for (index, line) in text.lines().enumerate() {
+			let line_number = index.saturating_add(1);
+			let mut fields = line
+				.split(|c: char| c == ',' || c.is_whitespace())
+				.filter(|field| !field.is_empty());
+
+			let Some(word) = fields.next() else {
+				continue;
+			};
+			let count = fields
+				.next()
+				.ok_or_else(|| (line_number, format!("no count for {word}")))?;
+			let count: u64 = count
+				.parse()
+				.map_err(|_| (line_number, format!("{count} is not a count")))?;
+			let total = counts.entry(word.to_lowercase()).or_default();
+
+			*total = total.saturating_add(count);
+		}
+
+		Ok(Self { counts })
+	}
+
+	#[must_use]
+	pub fn get(&self, word: &str) -> Option<u64> {
+		self.counts.get(word).copied()
+	}
+
+	/// Whether `word` is used at least `min` times. Unknown words are used 0
+	/// times.
+	#[must_use]
+	pub fn is_common(&self, word: &str, min: u64) -> bool {
+		self.get(word).unwrap_or_default() >= min
+	}
+
+	#[must_use]
+	pub fn len(&self) -> usize {
+		self.counts.len()
+	}
+
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.counts.is_empty()
+	}
+}
+
+impl<S: Into<String>> FromIterator<(S, u64)> for WordFrequencies {
+	fn from_iter<I: IntoIterator<Item = (S, u64)>>(iter: I) -> Self {
+		Self {
+			counts: iter
+				.into_iter()
+				.map(|(word, count)| (word.into().to_lowercase(), count))
+				.collect(),
+		}
+	}
+}
+
+/// # Errors
+/// Propagates errors from `DictionaryRegistry::get`.
+pub fn get_dictionary(dict: &Dictionary) -> Result<&'static [&'static str], DataError> {
+	DictionaryRegistry::global().get(dict)
 }
 
 /// # Errors
-/// Returns an error if the dictionary is not found.
-pub fn get_dictionary(dict: &Dictionary) -> Result<&'static Vec<&'static str>, DataError> {
-	DICTIONARIES
-		.get(dict.name())
-		.ok_or_else(|| DataError::DictionaryNotFound(dict.name().to_string()))
+/// Propagates errors from `DictionaryRegistry::index`.
+pub fn get_dictionary_index(dict: &Dictionary) -> Result<&'static DictionaryIndex, DataError> {
+	DictionaryRegistry::global().index(dict)
+}
+
+/// A dictionary's words with their lengths and the letters they use, so
+/// lookups can skip words of the wrong length or with the wrong letters
+/// without looking at them.
+#[derive(Debug)]
+pub struct DictionaryIndex {
+	words: &'static [&'static str],
+	/// length and letter mask of each word, in dictionary order
+	entries: Vec<(usize, u32)>,
+	/// positions of words in `words`, by length
+	by_length: Vec<Vec<usize>>,
+}
+
+impl DictionaryIndex {
+	#[must_use]
+	pub fn new(words: &'static [&'static str]) -> Self {
+		let entries: Vec<(usize, u32)> = words
+			.iter()
+			.map(|word| (word.chars().count(), letter_mask(word)))
+			.collect();
+		let mut by_length: Vec<Vec<usize>> = vec![];
+
+		for (position, (length, _)) in entries.iter().enumerate() {
+			if by_length.len() <= *length {
+				by_length.resize_with(length.saturating_add(1), Vec::new);
+			}
+
+			if let Some(positions) = by_length.get_mut(*length) {
+				positions.push(position);
+			}
+		}
+
+		Self {
+			words,
+			entries,
+			by_length,
+		}
+	}
+
+	#[must_use]
+	pub const fn words(&self) -> &'static [&'static str] {
+		self.words
+	}
+
+	/// Words between `min_length` and `max_length` letters long that use every
+	/// letter in the `required` mask and none in the `forbidden` mask, in
+	/// dictionary order. See `letter_mask`.
+	pub fn candidates(
+		&self,
+		min_length: usize,
+		max_length: Option<usize>,
+		required: u32,
+		forbidden: u32,
+	) -> impl Iterator<Item = &'static str> + '_ {
+		let positions: Box<dyn Iterator<Item = usize> + '_> = if max_length == Some(min_length) {
+			Box::new(
+				self.by_length
+					.get(min_length)
+					.into_iter()
+					.flatten()
+					.copied(),
+			)
+		} else {
+			Box::new(0..self.words.len())
+		};
+
+		positions.filter_map(move |position| {
+			let (length, mask) = self.entries.get(position)?;
+			let is_candidate = *length >= min_length
+				&& max_length.is_none_or(|max| *length <= max)
+				&& mask & required == required
+				&& mask & forbidden == 0;
+
+			is_candidate
+				.then(|| self.words.get(position).copied())
+				.flatten()
+		})
+	}
+}
+
+/// A bit for each letter a-z in `letters`, `a` being the lowest. Other
+/// characters are ignored.
+#[must_use]
+pub fn letter_mask(letters: &str) -> u32 {
+	letters
+		.bytes()
+		.filter(u8::is_ascii_lowercase)
+		.fold(0, |mask, c| {
+			mask | 1_u32
+				.checked_shl(u32::from(c.saturating_sub(b'a')))
+				.unwrap_or_default()
+		})
+}
+
+fn load_dictionary(dict: &Dictionary) -> Result<&'static [&'static str], DataError> {
+	let words = match dict {
+		Dictionary::Custom { path, .. } => read_custom_dictionary(path)?,
+		_ => read_embedded_dictionary(dict)?,
+	};
+
+	if words.is_empty() {
+		return Err(DataError::EmptyDictionary(match dict {
+			Dictionary::Custom { path, .. } => path.display().to_string(),
+			_ => dict.name().to_string(),
+		}));
+	}
+
+	Ok(Box::leak(words.into_boxed_slice()))
 }
 
-fn load_dict_data() -> HashMap<&'static str, Cow<'static, [u8]>> {
-	let mut data = HashMap::new();
+fn read_embedded_dictionary(dict: &Dictionary) -> Result<Vec<&'static str>, DataError> {
+	let asset = dict
+		.asset()
+		.ok_or_else(|| DataError::DictionaryNotFound(dict.name().to_string()))?;
+	let data = Assets::get(asset)
+		.ok_or_else(|| DataError::DictionaryNotFound(dict.name().to_string()))?
+		.data;
+	let invalid_utf8 = |e: str::Utf8Error| DataError::InvalidUtf8 {
+		path: asset.to_string(),
+		cause: e.to_string(),
+	};
+	let text: &'static str = match data {
+		Cow::Borrowed(bytes) => str::from_utf8(bytes).map_err(invalid_utf8)?,
+		Cow::Owned(bytes) => Box::leak(
+			String::from_utf8(bytes)
+				.map_err(|e| invalid_utf8(e.utf8_error()))?
+				.into_boxed_str(),
+		),
+	};
+
+	Ok(text.lines().filter_map(non_empty_str).collect())
+}
+
+fn read_custom_dictionary(path: &Path) -> Result<Vec<&'static str>, DataError> {
+	let files = if path.is_dir() {
+		let mut files = fs::read_dir(path)
+			.map_err(|e| read_error(path, &e))?
+			.map(|entry| entry.map(|e| e.path()))
+			.collect::<Result<Vec<_>, _>>()
+			.map_err(|e| read_error(path, &e))?;
+
+		files.retain(|file| file.is_file());
+		files.sort();
+		files
+	} else {
+		vec![path.to_path_buf()]
+	};
+
+	let mut words: Vec<&'static str> = vec![];
+
+	for file in files {
+		let bytes = fs::read(&file).map_err(|e| read_error(&file, &e))?;
+		let text = String::from_utf8(bytes).map_err(|e| DataError::InvalidUtf8 {
+			path: file.display().to_string(),
+			cause: e.utf8_error().to_string(),
+		})?;
+		let text: &'static str = Box::leak(text.to_lowercase().into_boxed_str());
+
+		words.extend(text.lines().filter_map(non_empty_str));
+	}
+
+	Ok(words)
+}
+
+fn read_error(path: &Path, error: &io::Error) -> DataError {
+	if error.kind() == io::ErrorKind::NotFound {
+		DataError::DictionaryNotFound(path.display().to_string())
+	} else {
+		DataError::ReadFailed {
+			path: path.display().to_string(),
+			cause: error.to_string(),
+		}
+	}
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod custom_dictionary_tests {
+	use super::*;
+
+	fn temp_path(name: &str) -> PathBuf {
+		let path = std::env::temp_dir().join(format!("wrd-data-{}-{name}", std::process::id()));
+
+		if path.is_dir() {
+			fs::remove_dir_all(&path).unwrap();
+		}
+
+		path
+	}
+
+	#[test]
+	fn should_load_words_from_file() {
+		let path = temp_path("words.txt");
+
+		fs::write(&path, "Crane\n\n  slate \nplate\n").unwrap();
+
+		let dict = Dictionary::custom(&path);
 
-	for dict in [Dictionary::Moby, Dictionary::Gwicks] {
-		data.insert(dict.name(), load_data(dict.asset()));
+		assert_eq!(
+			dict.name(),
+			format!("wrd-data-{}-words", std::process::id())
+		);
+		assert_eq!(get_dictionary(&dict).unwrap(), ["crane", "slate", "plate"]);
 	}
 
-	data
+	#[test]
+	fn should_load_words_from_directory() {
+		let path = temp_path("dir");
+
+		fs::create_dir_all(&path).unwrap();
+		fs::write(path.join("b.txt"), "yenta\n").unwrap();
+		fs::write(path.join("a.txt"), "llama\neerie\n").unwrap();
+
+		assert_eq!(
+			get_dictionary(&Dictionary::custom(&path)).unwrap(),
+			["llama", "eerie", "yenta"]
+		);
+	}
+
+	#[test]
+	fn should_error_on_unusable_files() {
+		let missing = temp_path("missing.txt");
+
+		assert_eq!(
+			get_dictionary(&Dictionary::custom(&missing)).unwrap_err(),
+			DataError::DictionaryNotFound(missing.display().to_string())
+		);
+
+		let empty = temp_path("empty.txt");
+
+		fs::write(&empty, "\n \n").unwrap();
+
+		assert_eq!(
+			get_dictionary(&Dictionary::custom(&empty)).unwrap_err(),
+			DataError::EmptyDictionary(empty.display().to_string())
+		);
+
+		let binary = temp_path("binary.txt");
+
+		fs::write(&binary, [0x66, 0x6f, 0xff, 0x0a]).unwrap();
+
+		assert!(matches!(
+			get_dictionary(&Dictionary::custom(&binary)).unwrap_err(),
+			DataError::InvalidUtf8 { .. }
+		));
+	}
 }
 
-fn parse_dict_data() -> HashMap<&'static str, Vec<&'static str>> {
-	let mut dicts = HashMap::new();
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod index_tests {
+	use super::*;
 
-	for dict in [Dictionary::Moby, Dictionary::Gwicks] {
-		dicts.insert(dict.name(), parse_data(dict.name()));
+	static WORDS: [&str; 7] = ["a", "crane", "eerie", "yenta", "shed", "cranes", "sled"];
+
+	#[test]
+	fn should_mask_letters() {
+		assert_eq!(letter_mask("a"), 1);
+		assert_eq!(letter_mask("abba"), 0b11);
+		assert_eq!(letter_mask("z"), 1 << 25);
+		assert_eq!(letter_mask("a-Z'"), 1);
 	}
 
-	dicts
+	#[test]
+	fn should_find_candidates_by_length_and_letters() {
+		let index = DictionaryIndex::new(&WORDS);
+		let candidates = |min: usize, max: Option<usize>, required: &str, forbidden: &str| {
+			index
+				.candidates(min, max, letter_mask(required), letter_mask(forbidden))
+				.collect::<Vec<_>>()
+		};
+
+		assert_eq!(
+			candidates(5, Some(5), "", ""),
+			vec!["crane", "eerie", "yenta"]
+		);
+		assert_eq!(candidates(5, Some(5), "e", "c"), vec!["eerie", "yenta"]);
+		assert_eq!(
+			candidates(4, None, "e", ""),
+			vec!["crane", "eerie", "yenta", "shed", "cranes", "sled"]
+		);
+		assert_eq!(candidates(4, Some(5), "s", ""), vec!["shed", "sled"]);
+		assert_eq!(candidates(0, None, "", "e"), vec!["a"]);
+		assert!(candidates(9, Some(9), "", "").is_empty());
+	}
+
+	#[test]
+	fn should_find_index_of_loaded_words() {
+		let registry = DictionaryRegistry::default();
+		let words = registry.get(&Dictionary::Gwicks).unwrap();
+		let index = registry.index_of(words).unwrap();
+
+		assert!(std::ptr::eq(
+			index,
+			registry.index(&Dictionary::Gwicks).unwrap()
+		));
+		assert!(std::ptr::eq(index.words(), words));
+		let copy: Vec<&str> = words.to_vec();
+
+		assert!(registry.index_of(&copy).is_none());
+	}
 }
 
-#[allow(clippy::panic)]
-fn load_data(name: &str) -> Cow<'static, [u8]> {
-	Assets::get(name)
-		.unwrap_or_else(|| panic!("could not load {name}"))
-		.data
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod registry_tests {
+	use super::*;
+
+	#[test]
+	fn should_list_bundled_dictionaries() {
+		let registry = DictionaryRegistry::default();
+
+		assert_eq!(
+			registry.dictionaries(),
+			vec![Dictionary::Moby, Dictionary::Gwicks]
+		);
+		assert_eq!(registry.find("gwicks"), Some(Dictionary::Gwicks));
+		assert_eq!(registry.find("nope"), None);
+	}
+
+	#[test]
+	fn should_load_bundled_dictionaries_on_demand() {
+		let registry = DictionaryRegistry::default();
+		let words = registry.get(&Dictionary::Gwicks).unwrap();
+
+		assert!(words.contains(&"crane"));
+		assert!(std::ptr::eq(
+			words,
+			registry.get(&Dictionary::Gwicks).unwrap()
+		));
+	}
+
+	#[test]
+	fn should_register_named_dictionaries() {
+		let registry = DictionaryRegistry::default();
+		let house = Dictionary::Custom {
+			name: "house".to_string(),
+			path: PathBuf::from("house.txt"),
+		};
+
+		registry.register(house.clone()).unwrap();
+		registry.register(house.clone()).unwrap();
+
+		assert_eq!(
+			registry.dictionaries(),
+			vec![Dictionary::Moby, Dictionary::Gwicks, house.clone()]
+		);
+		assert_eq!(registry.find("house"), Some(house.clone()));
+		assert_eq!(
+			registry.register(Dictionary::custom("elsewhere/house.txt")),
+			Err(DataError::DuplicateDictionary("house".to_string()))
+		);
+		assert_eq!(
+			registry.register(Dictionary::custom("moby.txt")),
+			Err(DataError::DuplicateDictionary("moby".to_string()))
+		);
+
+		registry.unregister(&house);
+
+		assert_eq!(registry.find("house"), None);
+	}
 }
 
-#[allow(clippy::panic)]
-fn parse_data(name: &str) -> Vec<&'static str> {
-	str::from_utf8(
-		DICT_DATA
-			.get(name)
-			.unwrap_or_else(|| panic!("{name} data not found")),
-	)
-	.unwrap_or_else(|e| panic!("could not read {name}: {e}"))
-	.lines()
-	.filter_map(non_empty_str)
-	.collect()
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod frequency_tests {
+	use super::*;
+
+	#[test]
+	fn should_parse_frequency_lists() {
+		let frequencies = WordFrequencies::parse("crane 120\nCrane,5\n\nslate\t7\n").unwrap();
+
+		assert_eq!(frequencies.len(), 2);
+		assert_eq!(frequencies.get("crane"), Some(125));
+		assert_eq!(frequencies.get("crare"), None);
+		assert!(frequencies.is_common("slate", 7));
+		assert!(!frequencies.is_common("crare", 1));
+		assert_eq!(
+			WordFrequencies::parse("crane 1\nslate\n").unwrap_err(),
+			(2, "no count for slate".to_string())
+		);
+		assert_eq!(
+			WordFrequencies::parse("crane lots").unwrap_err(),
+			(1, "lots is not a count".to_string())
+		);
+	}
+
+	#[test]
+	fn should_look_up_loaded_frequencies() {
+		let path = std::env::temp_dir().join(format!("wrd-freq-{}.txt", std::process::id()));
+		let dict = Dictionary::Custom {
+			name: "frequency-test".to_string(),
+			path: PathBuf::from("frequency-test.txt"),
+		};
+
+		fs::write(&path, "crane 40\nslate 2\n").unwrap();
+
+		assert_eq!(dict.frequency("crane"), None);
+
+		DictionaryRegistry::global()
+			.load_frequencies(&dict, &path)
+			.unwrap();
+
+		assert_eq!(dict.frequency("crane"), Some(40));
+		assert_eq!(dict.frequency("crare"), None);
+		assert!(matches!(
+			DictionaryRegistry::global()
+				.load_frequencies(&dict, &path.with_extension("missing"))
+				.unwrap_err(),
+			DataError::DictionaryNotFound(_)
+		));
+	}
 }
@@ -0,0 +1,194 @@
+use crate::notwordle::{GuessResultToken, NotwordleError};
+
+/// The feedback Wordle would show for `guess` when the answer is `answer`.
+///
+/// Letters in the right position are `Right`. Remaining letters are
+/// `WrongPosition` while the answer still has unmatched copies of that letter,
+/// left to right, and `Wrong` after that. So guessing "geese" for "eerie" gives
+/// `!g e ?e !s e`: only one of the two misplaced "e"s is marked as present.
+///
+/// # Errors
+/// Errors if `guess` and `answer` have a different number of characters.
+pub fn score_guess(guess: &str, answer: &str) -> Result<Vec<GuessResultToken>, NotwordleError> {
+	let guess: Vec<char> = guess.chars().collect();
+	let answer: Vec<char> = answer.chars().collect();
+
+	if guess.len() != answer.len() {
+		return Err(NotwordleError::MismatchedGuessLength {
+			guess: guess.len(),
+			answer: answer.len(),
+		});
+	}
+
+	let mut unmatched: Vec<char> = guess
+		.iter()
+		.zip(&answer)
+		.filter(|(g, a)| g != a)
+		.map(|(_, a)| *a)
+		.collect();
+
+	Ok(guess
+		.iter()
+		.zip(&answer)
+		.map(|(g, a)| {
+			let letter = g.to_string();
+
+			if g == a {
+				GuessResultToken::Right(letter)
+			} else if let Some(index) = unmatched.iter().position(|c| c == g) {
+				unmatched.swap_remove(index);
+				GuessResultToken::WrongPosition(letter)
+			} else {
+				GuessResultToken::Wrong(letter)
+			}
+		})
+		.collect())
+}
+
+/// Compact equivalent of `score_guess` for scoring many guesses quickly.
+/// Encodes the feedback as a base 3 number, one digit per position: 2 right,
+/// 1 wrong position, 0 wrong.
+///
+/// Compares byte by byte, so words are expected to be lowercase ascii.
+pub fn feedback_code(guess: &[u8], answer: &[u8]) -> u64 {
+	let mut unmatched = [0_u8; 256];
+
+	for (g, a) in guess.iter().zip(answer) {
+		if g != a
+			&& let Some(count) = unmatched.get_mut(usize::from(*a))
+		{
+			*count = count.saturating_add(1);
+		}
+	}
+
+	guess.iter().zip(answer).fold(0_u64, |code, (g, a)| {
+		let mark = if g == a {
+			2
+		} else {
+			match unmatched.get_mut(usize::from(*g)) {
+				Some(count) if *count > 0 => {
+					*count = count.saturating_sub(1);
+					1
+				}
+				_ => 0,
+			}
+		};
+
+		code.wrapping_mul(3).wrapping_add(mark)
+	})
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+	use super::*;
+	use crate::data::{Dictionary, get_dictionary};
+	use crate::notwordle::Notwordle;
+
+	fn scored(guess: &str, answer: &str) -> String {
+		score_guess(guess, answer)
+			.unwrap()
+			.iter()
+			.map(ToString::to_string)
+			.collect::<Vec<_>>()
+			.join(" ")
+	}
+
+	#[test]
+	fn should_score_guesses() {
+		assert_eq!(scored("crane", "crane"), "c r a n e");
+		assert_eq!(scored("plate", "pilot"), "p ?l !a ?t !e");
+		assert_eq!(scored("xxxxx", "crane"), "!x !x !x !x !x");
+		assert_eq!(scored("", ""), "");
+	}
+
+	#[test]
+	fn should_score_repeated_letters() {
+		assert_eq!(scored("hello", "llama"), "!h !e ?l ?l !o");
+		assert_eq!(scored("lolly", "llama"), "l !o ?l !l !y");
+		assert_eq!(scored("geese", "eerie"), "!g e ?e !s e");
+		assert_eq!(scored("eerie", "elder"), "e ?e ?r !i !e");
+		assert_eq!(scored("eerie", "horse"), "!e !e r !i e");
+		assert_eq!(scored("asses", "sassy"), "?a ?s s !e ?s");
+		// a right letter takes priority over an earlier misplaced copy
+		assert_eq!(scored("sissy", "bless"), "?s !i !s s !y");
+	}
+
+	#[test]
+	fn should_error_on_mismatched_lengths() {
+		assert_eq!(
+			score_guess("crane", "cranes").unwrap_err(),
+			NotwordleError::MismatchedGuessLength {
+				guess: 5,
+				answer: 6
+			}
+		);
+	}
+
+	#[test]
+	fn should_encode_feedback() {
+		// !l l a !m !a
+		assert_eq!(feedback_code(b"llama", b"plate"), 2 * 27 + 2 * 9);
+		assert_eq!(feedback_code(b"crane", b"crane"), 242);
+		assert_eq!(feedback_code(b"geese", b"eerie"), 2 * 27 + 9 + 2);
+		assert_eq!(feedback_code(b"xxxxx", b"crane"), 0);
+	}
+
+	fn five_letter_words() -> Vec<&'static str> {
+		get_dictionary(&Dictionary::Gwicks)
+			.unwrap()
+			.iter()
+			.filter(|w| w.len() == 5 && w.bytes().all(|b| b.is_ascii_lowercase()))
+			.copied()
+			.collect()
+	}
+
+	#[test]
+	fn should_agree_with_feedback_code() {
+		let words = five_letter_words();
+
+		for guess in words.iter().step_by(211) {
+			for answer in words.iter().step_by(53) {
+				let code = score_guess(guess, answer)
+					.unwrap()
+					.iter()
+					.fold(0_u64, |code, token| {
+						let mark = match token {
+							GuessResultToken::Right(_) => 2,
+							GuessResultToken::WrongPosition(_) => 1,
+							GuessResultToken::Wrong(_) => 0,
+						};
+
+						code.wrapping_mul(3).wrapping_add(mark)
+					});
+
+				assert_eq!(
+					code,
+					feedback_code(guess.as_bytes(), answer.as_bytes()),
+					"{guess} {answer}"
+				);
+			}
+		}
+	}
+
+	#[test]
+	fn should_never_refine_away_the_answer() {
+		let words = five_letter_words();
+		let guesses = ["eerie", "sassy", "llama", "crane", "geese", "pilot"];
+
+		for (i, answer) in words.iter().step_by(37).enumerate() {
+			let mut notwordle = Notwordle::default();
+
+			for guess in guesses.iter().cycle().skip(i).take(3) {
+				notwordle
+					.register_guess_tokens(score_guess(guess, answer).unwrap())
+					.unwrap();
+
+				assert!(
+					notwordle.refine(Some(&words)).unwrap().contains(answer),
+					"{answer} eliminated after guessing {guess}"
+				);
+			}
+		}
+	}
+}
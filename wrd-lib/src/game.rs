@@ -0,0 +1,455 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::feedback::score_guess;
+use crate::notwordle::GuessResultToken;
+
+const SECONDS_PER_DAY: u64 = 60 * 60 * 24;
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum GameError {
+	#[error("no {0} letter words to choose from")]
+	NoWords(usize),
+	#[error("guess must have {expected} letters, got {got}")]
+	WrongLength { expected: usize, got: usize },
+	#[error("{0} is not in the dictionary")]
+	NotInDictionary(String),
+	#[error("hard mode: letter {position} must be {letter}")]
+	MustKeepLetter { position: usize, letter: String },
+	#[error("hard mode: guess must contain {letter}")]
+	MustUseLetter { letter: String },
+	#[error("the game is over")]
+	GameOver,
+}
+
+/// How the answer is chosen
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SecretWord {
+	/// a word picked from a seed, the same seed always picks the same word
+	Seed(u64),
+	/// the word of the day, for a number of days since the unix epoch
+	Daily(u64),
+	/// a known answer, e.g. one chosen by a friend
+	Word(String),
+}
+
+impl SecretWord {
+	/// A seed from the current time
+	#[must_use]
+	pub fn random() -> Self {
+		let nanos = SystemTime::now()
+			.duration_since(UNIX_EPOCH)
+			.map(|d| d.as_nanos())
+			.unwrap_or_default();
+
+		Self::Seed(u64::try_from(nanos & u128::from(u64::MAX)).unwrap_or_default())
+	}
+
+	/// Today's word (UTC)
+	#[must_use]
+	pub fn today() -> Self {
+		let secs = SystemTime::now()
+			.duration_since(UNIX_EPOCH)
+			.map(|d| d.as_secs())
+			.unwrap_or_default();
+
+		Self::Daily(secs.checked_div(SECONDS_PER_DAY).unwrap_or_default())
+	}
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GameOptions {
+	pub word_length: usize,
+	pub max_guesses: usize,
+	/// revealed hints must be used in subsequent guesses
+	pub hard_mode: bool,
+}
+
+impl Default for GameOptions {
+	fn default() -> Self {
+		Self {
+			word_length: 5,
+			max_guesses: 6,
+			hard_mode: false,
+		}
+	}
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GameStatus {
+	InProgress,
+	Won,
+	Lost,
+}
+
+#[derive(Clone, Debug)]
+pub struct Game {
+	secret: SecretWord,
+	answer: String,
+	words: Vec<&'static str>,
+	options: GameOptions,
+	guesses: Vec<(String, Vec<GuessResultToken>)>,
+}
+
+impl Game {
+	/// Start a game with an answer chosen from `words` of the configured length.
+	/// Guesses are checked against the same words.
+	///
+	/// # Errors
+	/// Errors if there are no words of the configured length, or a
+	/// `SecretWord::Word` answer has the wrong length.
+	pub fn new(
+		words: &[&'static str],
+		secret: SecretWord,
+		options: GameOptions,
+	) -> Result<Self, GameError> {
+		let words: Vec<&'static str> = words
+			.iter()
+			.filter(|word| {
+				word.len() == options.word_length && word.bytes().all(|b| b.is_ascii_lowercase())
+			})
+			.copied()
+			.collect();
+		let answer = match &secret {
+			SecretWord::Word(word) => {
+				let word = word.trim().to_lowercase();
+				let got = word.chars().count();
+
+				if got != options.word_length {
+					return Err(GameError::WrongLength {
+						expected: options.word_length,
+						got,
+					});
+				}
+
+				word
+			}
+			SecretWord::Seed(seed) => pick_word(&words, *seed)
+				.ok_or(GameError::NoWords(options.word_length))?
+				.to_string(),
+			// salted so daily words don't follow the same sequence as seeds
+			SecretWord::Daily(day) => pick_word(&words, day ^ 0x5752_445f_4441_494c)
+				.ok_or(GameError::NoWords(options.word_length))?
+				.to_string(),
+		};
+
+		Ok(Self {
+			secret,
+			answer,
+			words,
+			options,
+			guesses: vec![],
+		})
+	}
+
+	/// Play a guess, returning its feedback.
+	///
+	/// # Errors
+	/// Errors if the game is over, the guess has the wrong length or is not in
+	/// the dictionary, or in hard mode does not use previously revealed hints.
+	pub fn guess(&mut self, word: &str) -> Result<&[GuessResultToken], GameError> {
+		if self.status() != GameStatus::InProgress {
+			return Err(GameError::GameOver);
+		}
+
+		let word = word.trim().to_lowercase();
+		let got = word.chars().count();
+
+		if got != self.options.word_length {
+			return Err(GameError::WrongLength {
+				expected: self.options.word_length,
+				got,
+			});
+		}
+
+		if word != self.answer && !self.words.contains(&word.as_str()) {
+			return Err(GameError::NotInDictionary(word));
+		}
+
+		if self.options.hard_mode {
+			self.check_hard_mode(&word)?;
+		}
+
+		let result = score_guess(&word, &self.answer).map_err(|_| GameError::WrongLength {
+			expected: self.options.word_length,
+			got,
+		})?;
+
+		self.guesses.push((word, result));
+
+		Ok(self
+			.guesses
+			.last()
+			.map(|(_, result)| result.as_slice())
+			.unwrap_or_default())
+	}
+
+	#[must_use]
+	pub fn status(&self) -> GameStatus {
+		let solved = self.guesses.last().is_some_and(|(_, result)| {
+			result
+				.iter()
+				.all(|token| matches!(token, GuessResultToken::Right(_)))
+		});
+
+		if solved {
+			GameStatus::Won
+		} else if self.guesses.len() >= self.options.max_guesses {
+			GameStatus::Lost
+		} else {
+			GameStatus::InProgress
+		}
+	}
+
+	/// Guessed words with their feedback, in order
+	#[must_use]
+	pub fn guesses(&self) -> &[(String, Vec<GuessResultToken>)] {
+		&self.guesses
+	}
+
+	#[must_use]
+	pub const fn remaining_guesses(&self) -> usize {
+		self.options.max_guesses.saturating_sub(self.guesses.len())
+	}
+
+	#[must_use]
+	pub const fn options(&self) -> &GameOptions {
+		&self.options
+	}
+
+	/// The answer. Only meant to be shown once the game is over.
+	#[must_use]
+	pub fn answer(&self) -> &str {
+		&self.answer
+	}
+
+	/// Spoiler free result grid for sharing, e.g.
+	///
+	/// ```text
+	/// wrd #20379 4/6*
+	///
+	/// ⬛🟨⬛⬛⬛
+	/// ⬛⬛🟩🟨⬛
+	/// 🟩⬛🟩⬛🟩
+	/// 🟩🟩🟩🟩🟩
+	/// ```
+	#[must_use]
+	pub fn share_grid(&self) -> String {
+		let id = match &self.secret {
+			SecretWord::Daily(day) => format!(" #{day}"),
+			SecretWord::Seed(seed) => format!(" seed {seed}"),
+			SecretWord::Word(_) => String::new(),
+		};
+		let score = match self.status() {
+			GameStatus::Won => self.guesses.len().to_string(),
+			_ => "X".to_string(),
+		};
+		let hard = if self.options.hard_mode { "*" } else { "" };
+		let rows: Vec<String> = self
+			.guesses
+			.iter()
+			.map(|(_, result)| result.iter().map(token_emoji).collect())
+			.collect();
+
+		format!(
+			"wrd{id} {score}/{}{hard}\n\n{}",
+			self.options.max_guesses,
+			rows.join("\n")
+		)
+	}
+
+	fn check_hard_mode(&self, word: &str) -> Result<(), GameError> {
+		let chars: Vec<char> = word.chars().collect();
+
+		for (_, result) in &self.guesses {
+			for (position, token) in result.iter().enumerate() {
+				if let GuessResultToken::Right(letter) = token
+					&& chars.get(position).map(char::to_string).as_ref() != Some(letter)
+				{
+					return Err(GameError::MustKeepLetter {
+						position: position.saturating_add(1),
+						letter: letter.clone(),
+					});
+				}
+			}
+
+			for token in result {
+				if let GuessResultToken::WrongPosition(letter) = token {
+					let needed = result
+						.iter()
+						.filter(|t| match t {
+							GuessResultToken::Right(c) | GuessResultToken::WrongPosition(c) => {
+								c == letter
+							}
+							GuessResultToken::Wrong(_) => false,
+						})
+						.count();
+
+					if word.matches(letter.as_str()).count() < needed {
+						return Err(GameError::MustUseLetter {
+							letter: letter.clone(),
+						});
+					}
+				}
+			}
+		}
+
+		Ok(())
+	}
+}
+
+const fn token_emoji(token: &GuessResultToken) -> char {
+	match token {
+		GuessResultToken::Right(_) => '🟩',
+		GuessResultToken::WrongPosition(_) => '🟨',
+		GuessResultToken::Wrong(_) => '⬛',
+	}
+}
+
+fn pick_word(words: &[&'static str], seed: u64) -> Option<&'static str> {
+	let len = u64::try_from(words.len()).ok()?;
+	let index = usize::try_from(splitmix64(seed).checked_rem(len)?).ok()?;
+
+	words.get(index).copied()
+}
+
+const fn splitmix64(seed: u64) -> u64 {
+	let mut z = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+	z = (z ^ z.wrapping_shr(30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+	z = (z ^ z.wrapping_shr(27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+	z ^ z.wrapping_shr(31)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+	use super::*;
+
+	static WORDS: [&str; 8] = [
+		"crane", "llama", "eerie", "sassy", "pilot", "plate", "stare", "entr'",
+	];
+
+	fn game(answer: &str, options: GameOptions) -> Game {
+		Game::new(&WORDS, SecretWord::Word(answer.to_string()), options).unwrap()
+	}
+
+	#[test]
+	fn should_pick_answers_deterministically() {
+		let options = GameOptions::default();
+		let a = Game::new(&WORDS, SecretWord::Seed(7), options).unwrap();
+		let b = Game::new(&WORDS, SecretWord::Seed(7), options).unwrap();
+
+		assert_eq!(a.answer(), b.answer());
+		assert!(WORDS.contains(&a.answer()));
+		assert_ne!(a.answer(), "entr'");
+
+		let daily = Game::new(&WORDS, SecretWord::Daily(20_379), options).unwrap();
+
+		assert!(WORDS.contains(&daily.answer()));
+		assert_eq!(
+			Game::new(
+				&WORDS,
+				SecretWord::Seed(1),
+				GameOptions {
+					word_length: 7,
+					..options
+				}
+			)
+			.unwrap_err(),
+			GameError::NoWords(7)
+		);
+	}
+
+	#[test]
+	fn should_play_to_a_win() {
+		let mut game = game("llama", GameOptions::default());
+
+		assert_eq!(
+			game.guess("Crane").unwrap(),
+			score_guess("crane", "llama").unwrap()
+		);
+		assert_eq!(game.status(), GameStatus::InProgress);
+
+		game.guess("llama").unwrap();
+
+		assert_eq!(game.status(), GameStatus::Won);
+		assert_eq!(game.guess("plate").unwrap_err(), GameError::GameOver);
+		assert_eq!(game.share_grid(), "wrd 2/6\n\n⬛⬛🟩⬛⬛\n🟩🟩🟩🟩🟩");
+	}
+
+	#[test]
+	fn should_lose_after_max_guesses() {
+		let mut game = game(
+			"llama",
+			GameOptions {
+				max_guesses: 2,
+				..GameOptions::default()
+			},
+		);
+
+		game.guess("crane").unwrap();
+		game.guess("pilot").unwrap();
+
+		assert_eq!(game.status(), GameStatus::Lost);
+		assert_eq!(game.remaining_guesses(), 0);
+		assert!(game.share_grid().starts_with("wrd X/2\n"));
+	}
+
+	#[test]
+	fn should_validate_guesses() {
+		let mut game = game("llama", GameOptions::default());
+
+		assert_eq!(
+			game.guess("cranes").unwrap_err(),
+			GameError::WrongLength {
+				expected: 5,
+				got: 6
+			}
+		);
+		assert_eq!(
+			game.guess("zzzzz").unwrap_err(),
+			GameError::NotInDictionary("zzzzz".to_string())
+		);
+		assert!(game.guesses().is_empty());
+	}
+
+	#[test]
+	fn should_enforce_hard_mode() {
+		let mut game = game(
+			"plate",
+			GameOptions {
+				hard_mode: true,
+				..GameOptions::default()
+			},
+		);
+
+		// !s ?t a !r e
+		game.guess("stare").unwrap();
+
+		assert_eq!(
+			game.guess("crane").unwrap_err(),
+			GameError::MustUseLetter {
+				letter: "t".to_string()
+			}
+		);
+		assert_eq!(
+			game.guess("pilot").unwrap_err(),
+			GameError::MustKeepLetter {
+				position: 3,
+				letter: "a".to_string()
+			}
+		);
+		assert_eq!(
+			game.guess("llama").unwrap_err(),
+			GameError::MustKeepLetter {
+				position: 5,
+				letter: "e".to_string()
+			}
+		);
+
+		game.guess("plate").unwrap();
+
+		assert_eq!(game.status(), GameStatus::Won);
+		assert!(game.share_grid().starts_with("wrd 2/6*\n"));
+	}
+}
@@ -0,0 +1,244 @@
+use std::collections::HashMap;
+
+use crate::data::{Dictionary, DictionaryRegistry, get_dictionary};
+use crate::match_words::MatchWordsError;
+
+/// Match words against a letter variable pattern, one character per letter:
+///
+/// - A-Z: a variable, the same variable is the same letter wherever it is used
+/// - a-z: this letter
+/// - *: any letter
+///
+/// `ABCA` matches "that" and "noun", `xAyA` matches "xaya". With `distinct`,
+/// different variables must be different letters, so `ABCA` still matches
+/// "that" but not "toot".
+///
+/// # Errors
+/// Errors if the pattern is empty or has characters other than A-Z, a-z and
+/// `*`.
+pub fn match_isomorph<'a>(
+	pattern: &str,
+	distinct: bool,
+	haystack: Option<&[&'a str]>,
+) -> Result<Vec<&'a str>, MatchWordsError> {
+	let pattern = pattern.trim();
+
+	if pattern.is_empty() {
+		return Err(MatchWordsError::EmptyPattern);
+	}
+
+	if let Some(c) = pattern
+		.chars()
+		.find(|c| !c.is_ascii_alphabetic() && *c != '*')
+	{
+		return Err(MatchWordsError::InvalidPattern {
+			token: pattern.to_string(),
+			reason: format!("unexpected '{c}', expected A-Z, a-z or *"),
+		});
+	}
+
+	let haystack =
+		haystack.unwrap_or_else(|| get_dictionary(&Dictionary::Moby).unwrap_or_default());
+
+	// without letters only the shape of a word matters, so whole groups of a
+	// loaded dictionary's words can be matched at once
+	if !pattern.chars().any(|c| c.is_ascii_lowercase())
+		&& let Some(index) = DictionaryRegistry::global().isomorph_index_of(haystack)
+	{
+		return Ok(index
+			.matching(pattern, distinct)
+			.into_iter()
+			.map(|word| -> &'a str { word })
+			.collect());
+	}
+
+	let result = haystack
+		.iter()
+		.filter(|word| is_isomorph_match(word, pattern, distinct))
+		.copied()
+		.collect();
+
+	Ok(result)
+}
+
+fn is_isomorph_match(word: &str, pattern: &str, distinct: bool) -> bool {
+	if word.chars().count() != pattern.chars().count() {
+		return false;
+	}
+
+	let mut bindings: Vec<(char, char)> = vec![];
+
+	word.chars().zip(pattern.chars()).all(|(c, p)| match p {
+		'*' => true,
+		'a'..='z' => c == p,
+		_ => match bindings.iter().find(|(variable, _)| *variable == p) {
+			Some((_, letter)) => *letter == c,
+			None if distinct && bindings.iter().any(|(_, letter)| *letter == c) => false,
+			None => {
+				bindings.push((p, c));
+				true
+			}
+		},
+	})
+}
+
+/// The letter variable pattern a word matches with distinct variables, e.g.
+/// `ABCA` for "that". Words with the same key can be swapped by a
+/// substitution cipher.
+#[must_use]
+pub fn isomorph_key(word: &str) -> String {
+	let mut seen: Vec<char> = vec![];
+
+	word.chars()
+		.map(|c| {
+			let index = seen.iter().position(|s| *s == c).unwrap_or_else(|| {
+				seen.push(c);
+				seen.len().saturating_sub(1)
+			});
+
+			u8::try_from(index)
+				.ok()
+				.and_then(|index| b'A'.checked_add(index))
+				.filter(u8::is_ascii_uppercase)
+				.map_or('*', char::from)
+		})
+		.collect()
+}
+
+/// Words grouped by their isomorph key, for solving substitution ciphers one
+/// word at a time
+#[derive(Clone, Debug, Default)]
+pub struct IsomorphIndex {
+	words: Vec<&'static str>,
+	/// positions in `words` for each key, ascending
+	groups: HashMap<String, Vec<usize>>,
+}
+
+impl IsomorphIndex {
+	#[must_use]
+	pub fn new(words: &[&'static str]) -> Self {
+		let mut groups: HashMap<String, Vec<usize>> = HashMap::new();
+
+		for (position, word) in words.iter().enumerate() {
+			groups.entry(isomorph_key(word)).or_default().push(position);
+		}
+
+		Self {
+			words: words.to_vec(),
+			groups,
+		}
+	}
+
+	/// Words a ciphertext word could decode to, in dictionary order. Case is
+	/// ignored.
+	pub fn candidates(&self, ciphertext: &str) -> impl Iterator<Item = &'static str> + '_ {
+		self.groups
+			.get(&isomorph_key(&ciphertext.trim().to_lowercase()))
+			.into_iter()
+			.flatten()
+			.filter_map(|position| self.words.get(*position).copied())
+	}
+
+	/// Words matching a pattern of variables and `*`, see `match_isomorph`,
+	/// in dictionary order. Letters in the pattern are not supported.
+	#[must_use]
+	pub fn matching(&self, pattern: &str, distinct: bool) -> Vec<&'static str> {
+		let mut positions: Vec<usize> = vec![];
+
+		for (key, group) in &self.groups {
+			if key.contains('*') {
+				// keys past 26 distinct letters use *, so check those words one by one
+				positions.extend(group.iter().filter(|position| {
+					self.words
+						.get(**position)
+						.is_some_and(|word| is_isomorph_match(word, pattern, distinct))
+				}));
+			} else if is_isomorph_match(key, pattern, distinct) {
+				positions.extend(group);
+			}
+		}
+
+		positions.sort_unstable();
+		positions
+			.into_iter()
+			.filter_map(|position| self.words.get(position).copied())
+			.collect()
+	}
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+	use super::*;
+
+	static WORDS: [&str; 9] = [
+		"eerie", "noon", "noun", "that", "tort", "toot", "xaya", "xray", "zoom",
+	];
+
+	#[test]
+	fn should_match_letter_variables() {
+		let matched = |pattern: &str, distinct: bool| {
+			match_isomorph(pattern, distinct, Some(&WORDS)).unwrap()
+		};
+
+		assert_eq!(
+			matched("ABCA", false),
+			vec!["noon", "noun", "that", "tort", "toot"]
+		);
+		assert_eq!(matched("ABCA", true), vec!["noun", "that", "tort"]);
+		assert_eq!(matched("ABBA", false), vec!["noon", "toot"]);
+		assert_eq!(matched("xAyA", false), vec!["xaya"]);
+		assert_eq!(matched("x***", false), vec!["xaya", "xray"]);
+		assert_eq!(matched("AABCA", true), vec!["eerie"]);
+	}
+
+	#[test]
+	fn should_error_on_invalid_isomorph_pattern() {
+		assert_eq!(
+			match_isomorph(" ", false, Some(&WORDS)).unwrap_err(),
+			MatchWordsError::EmptyPattern
+		);
+		assert_eq!(
+			match_isomorph("AB A", false, Some(&WORDS))
+				.unwrap_err()
+				.to_string(),
+			"invalid pattern AB A: unexpected ' ', expected A-Z, a-z or *"
+		);
+	}
+
+	#[test]
+	fn should_index_words_by_isomorph_key() {
+		assert_eq!(isomorph_key("that"), "ABCA");
+		assert_eq!(isomorph_key("eerie"), "AABCA");
+
+		let index = IsomorphIndex::new(&WORDS);
+
+		assert_eq!(
+			index.candidates("GSZG").collect::<Vec<_>>(),
+			vec!["noun", "that", "tort"]
+		);
+		assert_eq!(
+			index.candidates("qrrq").collect::<Vec<_>>(),
+			vec!["noon", "toot"]
+		);
+		assert_eq!(index.candidates("abcdefg").count(), 0);
+		assert_eq!(
+			index.matching("ABCA", false),
+			vec!["noon", "noun", "that", "tort", "toot"]
+		);
+		assert_eq!(index.matching("*BB*", false), vec!["noon", "toot", "zoom"]);
+	}
+
+	#[test]
+	fn should_match_shapes_with_a_cached_index() {
+		let dict = get_dictionary(&Dictionary::Gwicks).unwrap();
+		let scanned: Vec<&str> = dict
+			.iter()
+			.filter(|word| is_isomorph_match(word, "ABCA*", true))
+			.copied()
+			.collect();
+
+		assert!(!scanned.is_empty());
+		assert_eq!(match_isomorph("ABCA*", true, Some(dict)).unwrap(), scanned);
+	}
+}
@@ -0,0 +1,178 @@
+use crate::match_words::MatchWordsError;
+
+pub const VOWELS: &str = "aeiou";
+pub const CONSONANTS: &str = "bcdfghjklmnpqrstvwxyz";
+
+/// Named groups of letters that can stand in for a letter list, in patterns
+/// and in the include, exclude and within letters.
+///
+/// `V` (vowels) and `C` (consonants) are always available. Other sets are
+/// defined as `name=letters` and used as `@name`, e.g. `rare=jqxz` and
+/// `@rare`. A set name runs to the next character that is not a-z, 0-9 or
+/// `_`, so put any other letters before it: `ab@rare`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LetterSets {
+	named: Vec<(String, String)>,
+}
+
+impl LetterSets {
+	/// Define a set from `name=letters` (the leading `@` is optional). The
+	/// letters can use ranges, classes and sets defined earlier. Redefining a
+	/// set replaces it.
+	///
+	/// # Errors
+	/// Errors if the name is empty or not a-z, 0-9 or `_`, or the letters are
+	/// invalid.
+	pub fn define(&mut self, definition: &str) -> Result<(), MatchWordsError> {
+		let definition = definition.trim();
+		let invalid = |reason: String| MatchWordsError::InvalidLetterSet {
+			definition: definition.to_string(),
+			reason,
+		};
+		let Some((name, letters)) = definition
+			.strip_prefix('@')
+			.unwrap_or(definition)
+			.split_once('=')
+		else {
+			return Err(invalid("expected name=letters".to_string()));
+		};
+
+		if name.is_empty() || !name.chars().all(is_name_char) {
+			return Err(invalid(format!("set name {name} must be a-z, 0-9 or _")));
+		}
+
+		let letters = self.parse(letters.trim()).map_err(invalid)?;
+		let letters = letters.0 + &letters.1.concat();
+
+		if let Some(set) = self.named.iter_mut().find(|(n, _)| n == name) {
+			set.1 = letters;
+		} else {
+			self.named.push((name.to_string(), letters));
+		}
+
+		Ok(())
+	}
+
+	/// Letters in the set called `name`
+	#[must_use]
+	pub fn get(&self, name: &str) -> Option<&str> {
+		self.named
+			.iter()
+			.find(|(n, _)| n == name)
+			.map(|(_, letters)| letters.as_str())
+	}
+
+	/// Expand ranges, classes and sets in a letter list to plain letters, e.g.
+	/// `a-cV` to `abcaeiou`.
+	///
+	/// # Errors
+	/// Errors on characters other than a-z, `-`, `V`, `C` and `@`, on ranges
+	/// that run backwards or have no end, and on unknown sets.
+	pub fn expand(&self, letters: &str) -> Result<String, MatchWordsError> {
+		self.parse(letters)
+			.map(|(plain, classes)| plain + &classes.concat())
+			.map_err(|reason| MatchWordsError::InvalidLetters {
+				letters: letters.to_string(),
+				reason,
+			})
+	}
+
+	/// Split a letter list into its plain letters and the letters of each
+	/// class or set it mentions, e.g. `abV` to `ab` and `[aeiou]`
+	pub(crate) fn parse(&self, letters: &str) -> Result<(String, Vec<String>), String> {
+		let mut chars = letters.chars().peekable();
+		let mut plain = String::new();
+		let mut classes: Vec<String> = vec![];
+
+		while let Some(c) = chars.next() {
+			match c {
+				'V' => classes.push(VOWELS.to_string()),
+				'C' => classes.push(CONSONANTS.to_string()),
+				'@' => {
+					let mut name = String::new();
+
+					while let Some(c) = chars.next_if(|c| is_name_char(*c)) {
+						name.push(c);
+					}
+
+					let set = self
+						.get(&name)
+						.ok_or_else(|| format!("unknown set @{name}"))?;
+
+					classes.push(set.to_string());
+				}
+				'a'..='z' if chars.next_if_eq(&'-').is_some() => match chars.next() {
+					Some(end) if end.is_ascii_lowercase() && end >= c => plain.extend(c..=end),
+					Some(end) if end.is_ascii_lowercase() => {
+						return Err(format!("range {c}-{end} runs backwards"));
+					}
+					_ => return Err(format!("range {c}- needs an end letter")),
+				},
+				'a'..='z' => plain.push(c),
+				_ => return Err(format!("unexpected '{c}', expected a-z, V, C or @set")),
+			}
+		}
+
+		Ok((plain, classes))
+	}
+}
+
+const fn is_name_char(c: char) -> bool {
+	c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_'
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn should_expand_classes_and_sets() {
+		let mut sets = LetterSets::default();
+
+		sets.define("rare=jqxz").unwrap();
+		sets.define("@odd=@rareV").unwrap();
+
+		assert_eq!(sets.expand("a-cx").unwrap(), "abcx");
+		assert_eq!(sets.expand("V").unwrap(), VOWELS);
+		assert_eq!(sets.expand("tC").unwrap(), format!("t{CONSONANTS}"));
+		assert_eq!(sets.expand("ab@rare").unwrap(), "abjqxz");
+		assert_eq!(sets.get("odd"), Some("jqxzaeiou"));
+		assert_eq!(
+			sets.parse("eV@rareV").unwrap(),
+			(
+				"e".to_string(),
+				vec![VOWELS.to_string(), "jqxz".to_string(), VOWELS.to_string()]
+			)
+		);
+
+		sets.define("rare=q").unwrap();
+
+		assert_eq!(sets.get("rare"), Some("q"));
+	}
+
+	#[test]
+	fn should_error_on_invalid_sets() {
+		let mut sets = LetterSets::default();
+
+		assert_eq!(
+			sets.define("rare").unwrap_err().to_string(),
+			"invalid letter set rare: expected name=letters"
+		);
+		assert_eq!(
+			sets.define("Rare=jqxz").unwrap_err().to_string(),
+			"invalid letter set Rare=jqxz: set name Rare must be a-z, 0-9 or _"
+		);
+		assert_eq!(
+			sets.expand("@rare").unwrap_err().to_string(),
+			"invalid letters @rare: unknown set @rare"
+		);
+		assert_eq!(
+			sets.expand("aB").unwrap_err(),
+			MatchWordsError::InvalidLetters {
+				letters: "aB".to_string(),
+				reason: "unexpected 'B', expected a-z, V, C or @set".to_string()
+			}
+		);
+	}
+}
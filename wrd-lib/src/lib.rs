@@ -1,8 +1,37 @@
+mod bench;
 mod data;
+mod feedback;
+mod game;
+mod isomorph;
+mod letter_sets;
 mod match_words;
+mod multi_notwordle;
 mod notwordle;
+mod parallel;
+mod solver;
+mod sort;
 mod util;
 
-pub use crate::data::{Dictionary, get_dictionary};
-pub use crate::match_words::match_words;
-pub use crate::notwordle::{GuessResultToken, Notwordle};
+pub use crate::bench::{BenchError, BenchOptions, BenchReport, Strategy, bench};
+pub use crate::data::{
+	DataError, Dictionary, DictionaryIndex, DictionaryRegistry, WordFrequencies, get_dictionary,
+	get_dictionary_index, letter_mask,
+};
+pub use crate::feedback::score_guess;
+pub use crate::game::{Game, GameError, GameOptions, GameStatus, SecretWord};
+pub use crate::isomorph::{IsomorphIndex, isomorph_key, match_isomorph};
+pub use crate::letter_sets::{CONSONANTS, LetterSets, VOWELS};
+pub use crate::match_words::{
+	FailedConstraint, MatchWordsError, Matcher, MatcherToken, WithinMode, count_matches,
+	format_pattern, match_words, match_words_iter, match_words_with_sets, parse_pattern,
+};
+pub use crate::multi_notwordle::MultiNotwordle;
+pub use crate::notwordle::{
+	Contradiction, GuessResultToken, GuessTile, Notwordle, NotwordleError, format_guess_result,
+	tokenize_guess_result,
+};
+pub use crate::solver::{
+	GuessPool, Suggestion, guess_entropy, rank_guesses, rank_guesses_across_boards,
+	rank_guesses_across_boards_weighted, rank_guesses_weighted,
+};
+pub use crate::sort::{SortKey, scrabble_score, sort_words};
@@ -1,16 +1,26 @@
-use std::sync::LazyLock;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
 
 use regex::Regex;
 
-use crate::data::{Dictionary, get_dictionary};
-use crate::util::non_empty_str;
+use crate::data::{
+	Dictionary, DictionaryIndex, DictionaryRegistry, WordFrequencies, get_dictionary, letter_mask,
+};
+use crate::letter_sets::LetterSets;
+use crate::parallel;
+use crate::util::{char_counts, non_empty_str};
 
 #[derive(thiserror::Error, Debug, PartialEq, Eq)]
 pub enum MatchWordsError {
 	#[error("unexpected empty pattern")]
 	EmptyPattern,
-	#[error("invalid pattern {0}")]
-	InvalidPattern(String),
+	#[error("invalid pattern {token}: {reason}")]
+	InvalidPattern { token: String, reason: String },
+	#[error("invalid letters {letters}: {reason}")]
+	InvalidLetters { letters: String, reason: String },
+	#[error("invalid letter set {definition}: {reason}")]
+	InvalidLetterSet { definition: String, reason: String },
 	#[error("could not create matcher {pattern}: {cause}")]
 	CreateMatcherFailed { pattern: String, cause: String },
 }
@@ -19,11 +29,20 @@ pub enum MatchWordsError {
 pub enum MatcherToken {
 	MatchAnyChars,             // **
 	MatchAnyChar,              // *
-	MatchAnyCharIn(String),    // a-z
-	ExcludeAllCharsIn(String), // !a-z
+	MatchAnyCharIn(String),    // abc, a-f, V, @rare
+	ExcludeAllCharsIn(String), // !abc, !a-f, !C
+	Literal(String),           // "ing"
+	// *?, *{2,4}, abc{2,}
+	Repeated {
+		token: Box<Self>,
+		min: usize,
+		max: Option<usize>,
+	},
 }
 
 impl PartialEq for MatcherToken {
+	// literals are kept apart from letter sets matching the same letters
+	#[allow(clippy::match_same_arms)]
 	fn eq(&self, other: &Self) -> bool {
 		match (self, other) {
 			(
@@ -36,76 +55,703 @@ impl PartialEq for MatcherToken {
 				Self::MatchAnyCharIn(b) | Self::ExcludeAllCharsIn(b),
 			) => a == b,
 
+			(Self::Literal(a), Self::Literal(b)) => a == b,
+
+			(
+				Self::Repeated {
+					token: a,
+					min: a_min,
+					max: a_max,
+				},
+				Self::Repeated {
+					token: b,
+					min: b_min,
+					max: b_max,
+				},
+			) => a == b && a_min == b_min && a_max == b_max,
+
 			_ => false,
 		}
 	}
 }
 
+/// Formats as a pattern part, e.g. `abc`, `!abc`, `"ing"` or `*{2,4}`
+impl fmt::Display for MatcherToken {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::MatchAnyChars => write!(f, "**"),
+			Self::MatchAnyChar => write!(f, "*"),
+			Self::MatchAnyCharIn(chars) => write!(f, "{chars}"),
+			Self::ExcludeAllCharsIn(chars) => write!(f, "!{chars}"),
+			Self::Literal(letters) => write!(f, "\"{letters}\""),
+			Self::Repeated { token, min, max } => match (min, max) {
+				(0, Some(1)) => write!(f, "{token}?"),
+				(min, Some(max)) if min == max => write!(f, "{token}{{{min}}}"),
+				(min, Some(max)) => write!(f, "{token}{{{min},{max}}}"),
+				(min, None) => write!(f, "{token}{{{min},}}"),
+			},
+		}
+	}
+}
+
+/// Parses a single pattern part, e.g. `abc` or `*{2,4}`. Letter sets must
+/// already be expanded, as `Display` writes them
+impl FromStr for MatcherToken {
+	type Err = MatchWordsError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		tokenize(s.trim(), &LetterSets::default())
+	}
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for MatcherToken {
+	fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.collect_str(self)
+	}
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for MatcherToken {
+	fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		let part = <String as serde::Deserialize>::deserialize(deserializer)?;
+
+		part.parse().map_err(serde::de::Error::custom)
+	}
+}
+
+/// A check a word failed, see `Matcher::explain`. Positions count from 0.
+#[derive(Clone, Debug, PartialEq)]
+pub enum FailedConstraint {
+	/// the word is used less often than the minimum frequency
+	Uncommon { min: u64 },
+	/// the word has an excluded letter
+	Excluded(char),
+	/// the word has a letter outside the within letters, or more copies of it
+	/// than a rack has
+	NotWithin(char),
+	/// the word has fewer copies of a letter than it must include
+	Missing { letter: char, count: usize },
+	/// the word has no letter left over for an include class
+	MissingClass(String),
+	/// the letters at a position do not match the pattern token there
+	Position {
+		position: usize,
+		token: MatcherToken,
+		letters: String,
+	},
+	/// the word is a length the pattern can not match
+	Length {
+		length: usize,
+		min: usize,
+		max: Option<usize>,
+	},
+	/// the word does not match the pattern between its fixed positions
+	Pattern,
+	/// guess results allow between `min` and `max` copies of a letter
+	LetterCount {
+		letter: char,
+		count: usize,
+		min: usize,
+		max: Option<usize>,
+	},
+}
+
+impl fmt::Display for FailedConstraint {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Uncommon { min } => write!(f, "used fewer than {min} times"),
+			Self::Excluded(c) => write!(f, "has {c}, which is excluded"),
+			Self::NotWithin(c) => write!(f, "has {c} outside the within letters"),
+			Self::Missing { letter, count: 1 } => write!(f, "does not include {letter}"),
+			Self::Missing { letter, count } => {
+				write!(f, "includes {letter} fewer than {count} times")
+			}
+			Self::MissingClass(class) => write!(f, "has no letter left for one of {class}"),
+			Self::Position {
+				position,
+				token,
+				letters,
+			} => write!(
+				f,
+				"has {letters} at position {}, the pattern needs {token}",
+				position.saturating_add(1)
+			),
+			Self::Length { length, min, max } => {
+				write!(f, "has {length} letters, the pattern needs ")?;
+
+				match max {
+					Some(max) if max == min => write!(f, "{min}"),
+					Some(max) => write!(f, "{min} to {max}"),
+					None => write!(f, "{min} or more"),
+				}
+			}
+			Self::Pattern => write!(f, "does not match the pattern"),
+			Self::LetterCount {
+				letter,
+				count,
+				min,
+				max,
+			} => {
+				write!(f, "has {count} {letter}, the guess results need ")?;
+
+				match max {
+					Some(max) if max == min => write!(f, "exactly {min}"),
+					Some(max) => write!(f, "{min} to {max}"),
+					None => write!(f, "at least {min}"),
+				}
+			}
+		}
+	}
+}
+
+/// How letters in `within` may be used by a matching word
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WithinMode {
+	/// each letter may be used any number of times
+	Repeat,
+	/// each letter may be used as many times as it appears in `within`,
+	/// like tiles in a scrabble rack
+	Rack,
+}
+
+/// A pattern compiled with its include, exclude and within letters, to match
+/// any number of words without parsing them again
+#[derive(Clone, Debug)]
+pub struct Matcher {
+	regex: Regex,
+	tokens: Vec<MatcherToken>,
+	include: Vec<(char, usize)>,
+	include_classes: Vec<String>,
+	exclude: Vec<char>,
+	within: Option<(Vec<(char, usize)>, WithinMode)>,
+	min_length: usize,
+	max_length: Option<usize>,
+	/// letters every match uses, see `letter_mask`
+	required: u32,
+	/// letters no match uses
+	forbidden: u32,
+	/// matches must be used at least this many times
+	min_frequency: Option<(Arc<WordFrequencies>, u64)>,
+}
+
+impl Matcher {
+	/// # Errors
+	/// Propagates errors from `Matcher::with_sets`.
+	pub fn new(
+		pattern: &str,
+		include: &str,
+		exclude: &str,
+		within: &str,
+		within_mode: WithinMode,
+	) -> Result<Self, MatchWordsError> {
+		Self::with_sets(
+			pattern,
+			include,
+			exclude,
+			within,
+			within_mode,
+			&LetterSets::default(),
+		)
+	}
+
+	/// Compile a pattern with named letter sets usable as `@name` in the
+	/// pattern and letter lists. The pattern can define more sets for itself
+	/// with `@name=letters` parts, e.g. `@rare=jqxz * @rare **`.
+	///
+	/// Each class or set in `include` needs a letter of its own, so `VV`
+	/// matches words with at least two vowels.
+	///
+	/// # Errors
+	/// Propagates errors from `tokenize_pattern`, `LetterSets::expand` and
+	/// creating the regex.
+	pub fn with_sets(
+		pattern: &str,
+		include: &str,
+		exclude: &str,
+		within: &str,
+		within_mode: WithinMode,
+		sets: &LetterSets,
+	) -> Result<Self, MatchWordsError> {
+		let (tokens, sets) = tokenize_pattern(pattern, sets)?;
+		let (plain, mut include_classes) =
+			sets.parse(include)
+				.map_err(|reason| MatchWordsError::InvalidLetters {
+					letters: include.to_string(),
+					reason,
+				})?;
+		let mut matcher = Self::from_tokens(
+			&tokens,
+			&plain,
+			&sets.expand(exclude)?,
+			&sets.expand(within)?,
+			within_mode,
+		)?;
+
+		// smaller classes pick their letter first
+		include_classes.sort_by_key(String::len);
+		matcher.include_classes = include_classes;
+
+		Ok(matcher)
+	}
+
+	/// Compile tokens with plain include, exclude and within letters
+	///
+	/// # Errors
+	/// Errors if the regex can not be created.
+	pub fn from_tokens(
+		tokens: &[MatcherToken],
+		include: &str,
+		exclude: &str,
+		within: &str,
+		within_mode: WithinMode,
+	) -> Result<Self, MatchWordsError> {
+		let (min_length, max_length) = tokens.iter().map(token_length).fold(
+			(0, Some(0)),
+			|(min, max): (usize, Option<usize>), (token_min, token_max)| {
+				(
+					min.saturating_add(token_min),
+					max.zip(token_max)
+						.map(|(max, token_max)| max.saturating_add(token_max)),
+				)
+			},
+		);
+		let outside_within = if within.is_empty() {
+			0
+		} else {
+			!letter_mask(within) & letter_mask("abcdefghijklmnopqrstuvwxyz")
+		};
+
+		Ok(Self {
+			regex: regex_from_tokens(tokens)?,
+			tokens: tokens.to_vec(),
+			include: char_counts(include),
+			include_classes: vec![],
+			exclude: exclude.chars().collect(),
+			within: (!within.is_empty()).then(|| (char_counts(within), within_mode)),
+			min_length,
+			max_length,
+			required: letter_mask(include),
+			forbidden: letter_mask(exclude) | outside_within,
+			min_frequency: None,
+		})
+	}
+
+	/// Only match words used at least `min` times according to `frequencies`,
+	/// leaving out obscure words
+	#[must_use]
+	pub fn with_min_frequency(mut self, frequencies: Arc<WordFrequencies>, min: u64) -> Self {
+		self.min_frequency = Some((frequencies, min));
+		self
+	}
+
+	#[must_use]
+	pub fn is_match(&self, word: &str) -> bool {
+		if let Some((frequencies, min)) = &self.min_frequency
+			&& !frequencies.is_common(word, *min)
+		{
+			return false;
+		}
+
+		// word must not include any of these letters
+		if word.chars().any(|c| self.exclude.contains(&c)) {
+			return false;
+		}
+
+		let counts = char_counts(word);
+
+		// word can only contain letters within this group
+		if !counts.iter().all(|(c, n)| self.is_within(*c, *n)) {
+			return false;
+		}
+
+		// word must include all of these letters, as many times as they are repeated
+		if !self
+			.include
+			.iter()
+			.all(|(c, n)| letter_count(&counts, *c) >= *n)
+		{
+			return false;
+		}
+
+		self.missing_class(word).is_none() && self.regex.is_match(word)
+	}
+
+	/// Every check `word` fails, empty when it matches. Letters at fixed
+	/// positions are checked against the pattern token there, so patterns with
+	/// `**` or open repetitions can only say the word does not match.
+	#[must_use]
+	pub fn explain(&self, word: &str) -> Vec<FailedConstraint> {
+		let mut failed = vec![];
+
+		if let Some((frequencies, min)) = &self.min_frequency
+			&& !frequencies.is_common(word, *min)
+		{
+			failed.push(FailedConstraint::Uncommon { min: *min });
+		}
+
+		let counts = char_counts(word);
+
+		for (c, n) in &counts {
+			if self.exclude.contains(c) {
+				failed.push(FailedConstraint::Excluded(*c));
+			}
+
+			if !self.is_within(*c, *n) {
+				failed.push(FailedConstraint::NotWithin(*c));
+			}
+		}
+
+		for (c, n) in &self.include {
+			if letter_count(&counts, *c) < *n {
+				failed.push(FailedConstraint::Missing {
+					letter: *c,
+					count: *n,
+				});
+			}
+		}
+
+		if let Some(class) = self.missing_class(word) {
+			failed.push(FailedConstraint::MissingClass(class.clone()));
+		}
+
+		failed.extend(self.explain_pattern(word));
+		failed
+	}
+
+	fn explain_pattern(&self, word: &str) -> Vec<FailedConstraint> {
+		let length = word.chars().count();
+
+		if length < self.min_length || self.max_length.is_some_and(|max| length > max) {
+			return vec![FailedConstraint::Length {
+				length,
+				min: self.min_length,
+				max: self.max_length,
+			}];
+		}
+
+		if self.regex.is_match(word) {
+			return vec![];
+		}
+
+		// tokens before the first variable length token start at a known
+		// position, as do tokens after the last one counting from the end
+		let is_fixed = |token: &&MatcherToken| {
+			let (min, max) = token_length(token);
+
+			max == Some(min)
+		};
+		let prefix: Vec<&MatcherToken> = self.tokens.iter().take_while(is_fixed).collect();
+		let suffix: Vec<&MatcherToken> = if prefix.len() == self.tokens.len() {
+			vec![]
+		} else {
+			self.tokens.iter().rev().take_while(is_fixed).collect()
+		};
+		let mut failed = vec![];
+		let mut position: usize = 0;
+
+		for token in prefix {
+			let (token_length, _) = token_length(token);
+
+			failed.extend(explain_position(word, position, token));
+			position = position.saturating_add(token_length);
+		}
+
+		let mut end = length;
+		let mut failed_suffix = vec![];
+
+		for token in suffix {
+			let (token_length, _) = token_length(token);
+
+			end = end.saturating_sub(token_length);
+			failed_suffix.extend(explain_position(word, end, token));
+		}
+
+		failed.extend(failed_suffix.into_iter().rev());
+
+		if failed.is_empty() {
+			failed.push(FailedConstraint::Pattern);
+		}
+
+		failed
+	}
+
+	/// Whether a word can use `n` copies of `c`, given the within letters
+	fn is_within(&self, c: char, n: usize) -> bool {
+		self.within
+			.as_ref()
+			.is_none_or(|(within, within_mode)| match within_mode {
+				WithinMode::Repeat => letter_count(within, c) > 0,
+				WithinMode::Rack => letter_count(within, c) >= n,
+			})
+	}
+
+	/// Words matching, in order
+	pub fn filter<'w, I>(&self, words: I) -> impl Iterator<Item = &'w str>
+	where
+		I: IntoIterator<Item = &'w str>,
+	{
+		words.into_iter().filter(|word| self.is_match(word))
+	}
+
+	/// Number of words matching
+	pub fn count<'w, I>(&self, words: I) -> usize
+	where
+		I: IntoIterator<Item = &'w str>,
+	{
+		self.filter(words).count()
+	}
+
+	/// Words in an indexed dictionary matching, in order. Words of the wrong
+	/// length or with the wrong letters are skipped without being matched.
+	pub fn filter_index<'i>(
+		&'i self,
+		index: &'i DictionaryIndex,
+	) -> impl Iterator<Item = &'static str> + 'i {
+		index
+			.candidates(
+				self.min_length,
+				self.max_length,
+				self.required,
+				self.forbidden,
+			)
+			.filter(|word| self.is_match(word))
+	}
+
+	/// Words in the haystack matching, in order, found as they are needed.
+	/// Uses the dictionary index when the haystack is a loaded dictionary.
+	pub fn matches<'h, 'a>(
+		&'h self,
+		haystack: &'h [&'a str],
+	) -> impl Iterator<Item = &'a str> + 'h {
+		self.candidates(haystack).filter(|word| self.is_match(word))
+	}
+
+	/// Every word in the haystack matching, in order. Uses the dictionary index
+	/// when the haystack is a loaded dictionary, and all threads with the
+	/// `parallel` feature.
+	#[must_use]
+	pub fn find_all<'a>(&self, haystack: &[&'a str]) -> Vec<&'a str> {
+		// narrowing down with the index is quick, the regex is worth spreading out
+		let candidates: Vec<&str> = self.candidates(haystack).collect();
+
+		parallel::filter(&candidates, |word| self.is_match(word))
+	}
+
+	/// Words in the haystack that could match, narrowed down with the
+	/// dictionary index if there is one
+	fn candidates<'h, 'a>(
+		&self,
+		haystack: &'h [&'a str],
+	) -> Box<dyn Iterator<Item = &'a str> + 'h> {
+		match DictionaryRegistry::global().index_of(haystack) {
+			Some(index) => Box::new(
+				index
+					.candidates(
+						self.min_length,
+						self.max_length,
+						self.required,
+						self.forbidden,
+					)
+					.map(|word| -> &'a str { word }),
+			),
+			None => Box::new(haystack.iter().copied()),
+		}
+	}
+
+	/// The first class without a letter of its own in the word, besides the
+	/// plain letters it must include
+	fn missing_class(&self, word: &str) -> Option<&String> {
+		if self.include_classes.is_empty() {
+			return None;
+		}
+
+		let mut letters: Vec<char> = word.chars().collect();
+
+		for (c, n) in &self.include {
+			for _ in 0..*n {
+				if let Some(index) = letters.iter().position(|l| l == c) {
+					letters.swap_remove(index);
+				}
+			}
+		}
+
+		self.include_classes.iter().find(|class| {
+			letters
+				.iter()
+				.position(|l| class.contains(*l))
+				.map(|index| letters.swap_remove(index))
+				.is_none()
+		})
+	}
+}
+
+fn letter_count(counts: &[(char, usize)], c: char) -> usize {
+	counts
+		.iter()
+		.find(|(letter, _)| *letter == c)
+		.map_or(0, |(_, n)| *n)
+}
+
 /// # Errors
-/// Propagates errors from `tokenize_pattern` and `match_words_from_tokens`.
+/// Propagates errors from `match_words_with_sets`.
 pub fn match_words<'a>(
 	pattern: &str,
 	include: &str,
 	exclude: &str,
 	within: &str,
+	within_mode: WithinMode,
 	haystack: Option<&[&'a str]>,
 ) -> Result<Vec<&'a str>, MatchWordsError> {
-	let tokens = tokenize_pattern(pattern)?;
-	let result = match_words_from_tokens(&tokens, include, exclude, within, haystack)?;
+	match_words_with_sets(
+		pattern,
+		include,
+		exclude,
+		within,
+		within_mode,
+		&LetterSets::default(),
+		haystack,
+	)
+}
 
-	Ok(result)
+/// Like `match_words`, with named letter sets. See `Matcher::with_sets`.
+///
+/// # Errors
+/// Propagates errors from `Matcher::with_sets`.
+pub fn match_words_with_sets<'a>(
+	pattern: &str,
+	include: &str,
+	exclude: &str,
+	within: &str,
+	within_mode: WithinMode,
+	sets: &LetterSets,
+	haystack: Option<&[&'a str]>,
+) -> Result<Vec<&'a str>, MatchWordsError> {
+	let matcher = Matcher::with_sets(pattern, include, exclude, within, within_mode, sets)?;
+
+	Ok(matcher.find_all(haystack_or_default(haystack)))
 }
 
-const EMPTY_WORDS: Vec<&'static str> = vec![];
+/// Like `match_words`, finding words as they are needed, so taking the first
+/// few stops early.
+///
+/// # Errors
+/// Propagates errors from `Matcher::new`.
+pub fn match_words_iter<'a>(
+	pattern: &str,
+	include: &str,
+	exclude: &str,
+	within: &str,
+	within_mode: WithinMode,
+	haystack: Option<&'a [&'a str]>,
+) -> Result<impl Iterator<Item = &'a str> + 'a, MatchWordsError> {
+	let matcher = Matcher::new(pattern, include, exclude, within, within_mode)?;
+	let words = haystack_or_default(haystack);
 
+	Ok(matcher
+		.candidates(words)
+		.filter(move |word| matcher.is_match(word)))
+}
+
+/// Number of words `match_words` would match, without collecting them
+///
+/// # Errors
+/// Propagates errors from `Matcher::new`.
+pub fn count_matches(
+	pattern: &str,
+	include: &str,
+	exclude: &str,
+	within: &str,
+	within_mode: WithinMode,
+	haystack: Option<&[&str]>,
+) -> Result<usize, MatchWordsError> {
+	let matcher = Matcher::new(pattern, include, exclude, within, within_mode)?;
+	let words = haystack_or_default(haystack);
+
+	Ok(matcher.matches(words).count())
+}
+
+/// # Errors
+/// Propagates errors from `Matcher::from_tokens`.
 pub fn match_words_from_tokens<'a>(
 	tokens: &[MatcherToken],
 	include: &str,
 	exclude: &str,
 	within: &str,
+	within_mode: WithinMode,
 	haystack: Option<&[&'a str]>,
 ) -> Result<Vec<&'a str>, MatchWordsError> {
-	let regex = regex_from_tokens(tokens)?;
-	let empty = &EMPTY_WORDS;
-	let result: Vec<&str> = haystack
-		.unwrap_or_else(|| get_dictionary(&Dictionary::Moby).unwrap_or(empty))
+	let matcher = Matcher::from_tokens(tokens, include, exclude, within, within_mode)?;
+
+	Ok(matcher.find_all(haystack_or_default(haystack)))
+}
+
+/// Tokenize a pattern, e.g. `* r * nt !sy`, without building a matcher.
+/// Ranges, classes and `@name=letters` sets are expanded to their letters.
+///
+/// # Errors
+/// Errors if the pattern is empty or has an invalid part.
+pub fn parse_pattern(pattern: &str) -> Result<Vec<MatcherToken>, MatchWordsError> {
+	tokenize_pattern(pattern, &LetterSets::default()).map(|(tokens, _)| tokens)
+}
+
+/// Write tokens back as a pattern, which `parse_pattern` reads as the same
+/// tokens
+#[must_use]
+pub fn format_pattern(tokens: &[MatcherToken]) -> String {
+	tokens
 		.iter()
-		.filter(|word| match_word(word, &regex, include, exclude, within))
-		.copied()
-		.collect();
+		.map(ToString::to_string)
+		.collect::<Vec<_>>()
+		.join(" ")
+}
 
-	Ok(result)
+fn haystack_or_default<'a, 'h>(haystack: Option<&'h [&'a str]>) -> &'h [&'a str] {
+	haystack.unwrap_or_else(|| get_dictionary(&Dictionary::Moby).unwrap_or_default())
 }
 
-fn match_word(word: &str, matcher: &Regex, include: &str, exclude: &str, within: &str) -> bool {
-	// word can only contain letters within this group
-	if !within.is_empty() && word.chars().any(|c| !within.contains(c)) {
-		return false;
-	}
+/// Fewest and most letters a token can match, `None` for no limit
+/// The failed constraint if the letters of `word` from `position` on do not
+/// match a fixed length `token`
+fn explain_position(word: &str, position: usize, token: &MatcherToken) -> Option<FailedConstraint> {
+	let (token_length, _) = token_length(token);
+	let letters: String = word.chars().skip(position).take(token_length).collect();
+	let is_match = Regex::new(&format!("^{}$", regex_from_token(token)))
+		.is_ok_and(|regex| regex.is_match(&letters));
 
-	// word must include all of these letters
-	if !include.is_empty() && include.chars().any(|c| !word.contains(c)) {
-		return false;
-	}
+	(!is_match).then(|| FailedConstraint::Position {
+		position,
+		token: token.clone(),
+		letters,
+	})
+}
 
-	// word must not include any of these letters
-	if !exclude.is_empty() && exclude.chars().any(|c| word.contains(c)) {
-		return false;
-	}
+fn token_length(token: &MatcherToken) -> (usize, Option<usize>) {
+	match token {
+		MatcherToken::MatchAnyChars => (0, None),
+		MatcherToken::MatchAnyChar
+		| MatcherToken::MatchAnyCharIn(_)
+		| MatcherToken::ExcludeAllCharsIn(_) => (1, Some(1)),
+		MatcherToken::Literal(letters) => {
+			let length = letters.chars().count();
 
-	matcher.is_match(word)
+			(length, Some(length))
+		}
+		MatcherToken::Repeated { token, min, max } => {
+			let (token_min, token_max) = token_length(token);
+
+			(
+				token_min.saturating_mul(*min),
+				token_max
+					.zip(*max)
+					.map(|(token_max, max)| token_max.saturating_mul(max)),
+			)
+		}
+	}
 }
 
 fn regex_from_tokens(tokens: &[MatcherToken]) -> Result<Regex, MatchWordsError> {
-	let pattern = tokens
-		.iter()
-		.map(|token| match token {
-			MatcherToken::MatchAnyChars => r"[a-z]*".to_string(),
-			MatcherToken::MatchAnyChar => r"[a-z]".to_string(),
-			MatcherToken::MatchAnyCharIn(chars) => format!("[{chars}]"),
-			MatcherToken::ExcludeAllCharsIn(chars) => format!("([[a-z]--[{chars}]])"),
-		})
-		.collect::<String>();
+	let pattern = tokens.iter().map(regex_from_token).collect::<String>();
 	let bounded = format!("^{pattern}$");
 
 	Regex::new(&bounded).map_err(|e| MatchWordsError::CreateMatcherFailed {
@@ -114,8 +760,36 @@ fn regex_from_tokens(tokens: &[MatcherToken]) -> Result<Regex, MatchWordsError>
 	})
 }
 
-fn tokenize_pattern(input: &str) -> Result<Vec<MatcherToken>, MatchWordsError> {
-	let parts: Vec<_> = input.split(' ').filter_map(non_empty_str).collect();
+fn regex_from_token(token: &MatcherToken) -> String {
+	match token {
+		MatcherToken::MatchAnyChars => r"[a-z]*".to_string(),
+		MatcherToken::MatchAnyChar => r"[a-z]".to_string(),
+		MatcherToken::MatchAnyCharIn(chars) => format!("[{chars}]"),
+		MatcherToken::ExcludeAllCharsIn(chars) => format!("([[a-z]--[{chars}]])"),
+		MatcherToken::Literal(letters) => format!("(?:{})", regex::escape(letters)),
+		MatcherToken::Repeated { token, min, max } => format!(
+			"(?:{}){{{min},{}}}",
+			regex_from_token(token),
+			max.map(|max| max.to_string()).unwrap_or_default()
+		),
+	}
+}
+
+/// Tokenize a pattern, returning its tokens and the letter sets extended with
+/// any `@name=letters` parts it defines
+fn tokenize_pattern(
+	input: &str,
+	sets: &LetterSets,
+) -> Result<(Vec<MatcherToken>, LetterSets), MatchWordsError> {
+	let mut sets = sets.clone();
+	let (definitions, parts): (Vec<_>, Vec<_>) = input
+		.split(' ')
+		.filter_map(non_empty_str)
+		.partition(|part| part.starts_with('@') && part.contains('='));
+
+	for definition in definitions {
+		sets.define(definition)?;
+	}
 
 	if parts.is_empty() {
 		return Err(MatchWordsError::EmptyPattern);
@@ -135,83 +809,291 @@ fn tokenize_pattern(input: &str) -> Result<Vec<MatcherToken>, MatchWordsError> {
 			acc
 		})
 		.iter()
-		.map(|part| tokenize(part))
+		.map(|part| tokenize(part, &sets))
 		.collect::<Result<Vec<_>, _>>()?;
 
-	Ok(tokens)
+	Ok((tokens, sets))
 }
 
-#[allow(clippy::expect_used)]
-static MATCH_CHARS_TOKEN_REGEX: LazyLock<Regex> =
-	LazyLock::new(|| Regex::new(r"^(\!)?([a-z]+)$").expect("invalid match token regex"));
+fn tokenize(input: &str, sets: &LetterSets) -> Result<MatcherToken, MatchWordsError> {
+	let invalid = |reason: String| MatchWordsError::InvalidPattern {
+		token: input.to_string(),
+		reason,
+	};
 
-fn tokenize(input: &str) -> Result<MatcherToken, MatchWordsError> {
 	if input == "**" {
 		return Ok(MatcherToken::MatchAnyChars);
 	}
 
-	if input == "*" {
+	let (atom, suffix) = split_suffix(input).map_err(invalid)?;
+	let token = tokenize_atom(atom, sets).map_err(invalid)?;
+
+	if suffix.is_empty() {
+		return Ok(token);
+	}
+
+	let (min, max) = parse_repetition(suffix).map_err(invalid)?;
+
+	Ok(MatcherToken::Repeated {
+		token: Box::new(token),
+		min,
+		max,
+	})
+}
+
+/// Split `*{2,4}` into `*` and `{2,4}`, or `"ing"?` into `"ing"` and `?`
+fn split_suffix(input: &str) -> Result<(&str, &str), String> {
+	let end = if let Some(quoted) = input.strip_prefix('"') {
+		quoted
+			.find('"')
+			.map(|close| close.saturating_add(2))
+			.ok_or_else(|| "missing closing \"".to_string())?
+	} else {
+		input.find(['?', '{']).unwrap_or(input.len())
+	};
+
+	Ok(input.split_at(end))
+}
+
+fn tokenize_atom(atom: &str, sets: &LetterSets) -> Result<MatcherToken, String> {
+	if atom == "*" {
 		return Ok(MatcherToken::MatchAnyChar);
 	}
 
-	let Some(captures) = MATCH_CHARS_TOKEN_REGEX.captures(input) else {
-		return Err(MatchWordsError::InvalidPattern(input.to_string()));
+	if atom == "**" {
+		return Err("** already matches any number of letters".to_string());
+	}
+
+	if atom.contains('*') {
+		return Err("use * for any one letter or ** for any number of letters".to_string());
+	}
+
+	if let Some(quoted) = atom.strip_prefix('"') {
+		let letters = quoted.strip_suffix('"').unwrap_or(quoted);
+
+		if letters.is_empty() {
+			return Err("expected letters between quotes".to_string());
+		}
+
+		if let Some(c) = letters.chars().find(|c| !c.is_ascii_lowercase()) {
+			return Err(format!("unexpected '{c}' in quotes, expected a-z"));
+		}
+
+		return Ok(MatcherToken::Literal(letters.to_string()));
+	}
+
+	if let Some(letters) = atom.strip_prefix('!') {
+		if letters.is_empty() {
+			return Err("expected letters after !".to_string());
+		}
+
+		return Ok(MatcherToken::ExcludeAllCharsIn(expand(letters, sets)?));
+	}
+
+	if atom.is_empty() {
+		return Err("expected letters or * before a repetition".to_string());
+	}
+
+	Ok(MatcherToken::MatchAnyCharIn(expand(atom, sets)?))
+}
+
+/// Expand ranges, classes and sets in a pattern position, e.g. `a-dV` to
+/// `abcdaeiou`
+fn expand(letters: &str, sets: &LetterSets) -> Result<String, String> {
+	sets.parse(letters)
+		.map(|(plain, classes)| plain + &classes.concat())
+}
+
+/// Parse `?`, `{n}`, `{n,}` or `{n,m}` into a min and optional max count
+fn parse_repetition(suffix: &str) -> Result<(usize, Option<usize>), String> {
+	if suffix == "?" {
+		return Ok((0, Some(1)));
+	}
+
+	let Some(bounds) = suffix
+		.strip_prefix('{')
+		.and_then(|rest| rest.strip_suffix('}'))
+	else {
+		return Err(format!(
+			"unexpected {suffix}, expected ?, {{n}}, {{n,}} or {{n,m}}"
+		));
+	};
+	let parse_count = |count: &str| {
+		count
+			.trim()
+			.parse::<usize>()
+			.map_err(|_| format!("expected a number in {{{bounds}}}, got \"{count}\""))
+	};
+
+	let (min, max) = match bounds.split_once(',') {
+		None => {
+			let count = parse_count(bounds)?;
+
+			(count, Some(count))
+		}
+		Some((min, "")) => (parse_count(min)?, None),
+		Some((min, max)) => (parse_count(min)?, Some(parse_count(max)?)),
 	};
 
-	match (
-		captures.get(1).map(|c| c.as_str()),
-		captures.get(2).map(|c| c.as_str().to_owned()),
-	) {
-		(Some("!"), Some(letters)) => Ok(MatcherToken::ExcludeAllCharsIn(letters)),
-		(None, Some(letters)) => Ok(MatcherToken::MatchAnyCharIn(letters)),
-		_ => Err(MatchWordsError::InvalidPattern(input.to_string())),
+	if max.is_some_and(|max| max < min) {
+		return Err(format!("{{{bounds}}} has a minimum above its maximum"));
 	}
+
+	Ok((min, max))
 }
 
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod tokenize_tests {
 	use super::*;
+	use crate::letter_sets::{CONSONANTS, VOWELS};
 
 	#[test]
 	#[allow(clippy::panic)]
 	fn should_error_on_invalid_pattern() {
 		assert_eq!(
-			tokenize_pattern("").unwrap_err(),
+			tokenize_pattern("", &LetterSets::default()).unwrap_err(),
 			MatchWordsError::EmptyPattern
 		);
+
+		let invalid =
+			|pattern: &str| match tokenize_pattern(pattern, &LetterSets::default()).unwrap_err() {
+				MatchWordsError::InvalidPattern { token, reason } => format!("{token}: {reason}"),
+				err => panic!("unexpected error {err}"),
+			};
+
+		assert_eq!(
+			invalid("* abc !def ghi!de"),
+			"ghi!de: unexpected '!', expected a-z, V, C or @set"
+		);
+		assert_eq!(
+			invalid("45 "),
+			"45: unexpected '4', expected a-z, V, C or @set"
+		);
+		assert_eq!(
+			invalid("***"),
+			"***: use * for any one letter or ** for any number of letters"
+		);
 		assert_eq!(
-			tokenize_pattern("* abc !def ghi!de").unwrap_err(),
-			MatchWordsError::InvalidPattern("ghi!de".to_string())
+			invalid("ABC !def"),
+			"ABC: unexpected 'A', expected a-z, V, C or @set"
 		);
+		assert_eq!(invalid("* @rare"), "@rare: unknown set @rare");
+		assert_eq!(invalid("f-a"), "f-a: range f-a runs backwards");
+		assert_eq!(invalid("ab-"), "ab-: range b- needs an end letter");
+		assert_eq!(invalid("!"), "!: expected letters after !");
+		assert_eq!(invalid("\"ing"), "\"ing: missing closing \"");
+		assert_eq!(invalid("\"\""), "\"\": expected letters between quotes");
 		assert_eq!(
-			tokenize_pattern("45 ").unwrap_err(),
-			MatchWordsError::InvalidPattern("45".to_string())
+			invalid("\"iNg\""),
+			"\"iNg\": unexpected 'N' in quotes, expected a-z"
+		);
+		assert_eq!(
+			invalid("**?"),
+			"**?: ** already matches any number of letters"
+		);
+		assert_eq!(
+			invalid("*{2,4"),
+			"*{2,4: unexpected {2,4, expected ?, {n}, {n,} or {n,m}"
+		);
+		assert_eq!(invalid("*{a}"), "*{a}: expected a number in {a}, got \"a\"");
+		assert_eq!(
+			invalid("*{4,2}"),
+			"*{4,2}: {4,2} has a minimum above its maximum"
+		);
+		assert_eq!(
+			invalid("{2}"),
+			"{2}: expected letters or * before a repetition"
+		);
+	}
+
+	#[test]
+	fn should_tokenize_extended_pattern() {
+		assert_eq!(
+			tokenize_pattern(
+				"a-c !x-z *? *{2,4} \"ing\" s{1,} \"ed\"?",
+				&LetterSets::default()
+			)
+			.unwrap()
+			.0,
+			vec![
+				MatcherToken::MatchAnyCharIn("abc".to_string()),
+				MatcherToken::ExcludeAllCharsIn("xyz".to_string()),
+				MatcherToken::Repeated {
+					token: Box::new(MatcherToken::MatchAnyChar),
+					min: 0,
+					max: Some(1)
+				},
+				MatcherToken::Repeated {
+					token: Box::new(MatcherToken::MatchAnyChar),
+					min: 2,
+					max: Some(4)
+				},
+				MatcherToken::Literal("ing".to_string()),
+				MatcherToken::Repeated {
+					token: Box::new(MatcherToken::MatchAnyCharIn("s".to_string())),
+					min: 1,
+					max: None
+				},
+				MatcherToken::Repeated {
+					token: Box::new(MatcherToken::Literal("ed".to_string())),
+					min: 0,
+					max: Some(1)
+				},
+			]
 		);
 		assert_eq!(
-			tokenize_pattern("***").unwrap_err(),
-			MatchWordsError::InvalidPattern("***".to_string())
+			tokenize_pattern("ad-fz *{3}", &LetterSets::default())
+				.unwrap()
+				.0,
+			vec![
+				MatcherToken::MatchAnyCharIn("adefz".to_string()),
+				MatcherToken::Repeated {
+					token: Box::new(MatcherToken::MatchAnyChar),
+					min: 3,
+					max: Some(3)
+				},
+			]
 		);
+	}
+
+	#[test]
+	fn should_tokenize_classes_and_sets() {
+		let mut sets = LetterSets::default();
+
+		sets.define("rare=jqxz").unwrap();
+
+		let (tokens, sets) = tokenize_pattern("@hard=k@rare C !V @hard", &sets).unwrap();
+
 		assert_eq!(
-			tokenize_pattern("ABC !def").unwrap_err(),
-			MatchWordsError::InvalidPattern("ABC".to_string())
+			tokens,
+			vec![
+				MatcherToken::MatchAnyCharIn(CONSONANTS.to_string()),
+				MatcherToken::ExcludeAllCharsIn(VOWELS.to_string()),
+				MatcherToken::MatchAnyCharIn("kjqxz".to_string()),
+			]
 		);
+		assert_eq!(sets.get("hard"), Some("kjqxz"));
 	}
 
 	#[test]
 	fn should_tokenize_pattern() {
 		assert_eq!(
-			tokenize_pattern("**").unwrap(),
+			tokenize_pattern("**", &LetterSets::default()).unwrap().0,
 			vec![MatcherToken::MatchAnyChars]
 		);
 
 		assert_eq!(
-			tokenize_pattern("* ** **").unwrap(),
+			tokenize_pattern("* ** **", &LetterSets::default())
+				.unwrap()
+				.0,
 			vec![MatcherToken::MatchAnyChar, MatcherToken::MatchAnyChars,]
 		);
 
 		assert_eq!(
-			tokenize_pattern("* a !bcd ** ** ** ef * **").unwrap(),
+			tokenize_pattern("* a !bcd ** ** ** ef * **", &LetterSets::default())
+				.unwrap()
+				.0,
 			vec![
 				MatcherToken::MatchAnyChar,
 				MatcherToken::MatchAnyCharIn("a".to_string()),
@@ -240,7 +1122,8 @@ mod match_words_tests {
 		let tokens = vec![MatcherToken::MatchAnyChars];
 
 		assert_eq!(
-			match_words_from_tokens(&tokens, "", "", "", Some(&TEST_WORDS)).unwrap(),
+			match_words_from_tokens(&tokens, "", "", "", WithinMode::Repeat, Some(&TEST_WORDS))
+				.unwrap(),
 			&TEST_WORDS
 		);
 	}
@@ -250,12 +1133,28 @@ mod match_words_tests {
 		let tokens = vec![MatcherToken::MatchAnyChars];
 
 		assert_eq!(
-			match_words_from_tokens(&tokens, "", "", "gfjk", Some(&TEST_WORDS)).unwrap(),
+			match_words_from_tokens(
+				&tokens,
+				"",
+				"",
+				"gfjk",
+				WithinMode::Repeat,
+				Some(&TEST_WORDS)
+			)
+			.unwrap(),
 			vec!["fffggg", "jjkk"]
 		);
 
 		assert_eq!(
-			match_words_from_tokens(&tokens, "f", "", "gfjk", Some(&TEST_WORDS)).unwrap(),
+			match_words_from_tokens(
+				&tokens,
+				"f",
+				"",
+				"gfjk",
+				WithinMode::Repeat,
+				Some(&TEST_WORDS)
+			)
+			.unwrap(),
 			vec!["fffggg"]
 		);
 	}
@@ -270,7 +1169,8 @@ mod match_words_tests {
 		];
 
 		assert_eq!(
-			match_words_from_tokens(&tokens, "", "", "", Some(&TEST_WORDS)).unwrap(),
+			match_words_from_tokens(&tokens, "", "", "", WithinMode::Repeat, Some(&TEST_WORDS))
+				.unwrap(),
 			vec!["jjkk".to_string(), "kkll".to_string()]
 		);
 	}
@@ -287,7 +1187,8 @@ mod match_words_tests {
 		];
 
 		assert_eq!(
-			match_words_from_tokens(&tokens, "", "", "", Some(&TEST_WORDS)).unwrap(),
+			match_words_from_tokens(&tokens, "", "", "", WithinMode::Repeat, Some(&TEST_WORDS))
+				.unwrap(),
 			vec!["aaabbb".to_string(), "bbbccc".to_string()]
 		);
 
@@ -298,7 +1199,8 @@ mod match_words_tests {
 		];
 
 		assert_eq!(
-			match_words_from_tokens(&tokens, "", "", "", Some(&TEST_WORDS)).unwrap(),
+			match_words_from_tokens(&tokens, "", "", "", WithinMode::Repeat, Some(&TEST_WORDS))
+				.unwrap(),
 			vec!["yenta".to_string(), "yes".to_string()]
 		);
 
@@ -311,7 +1213,8 @@ mod match_words_tests {
 		];
 
 		assert_eq!(
-			match_words_from_tokens(&token, "", "", "", Some(&TEST_WORDS)).unwrap(),
+			match_words_from_tokens(&token, "", "", "", WithinMode::Repeat, Some(&TEST_WORDS))
+				.unwrap(),
 			vec!["fffggg".to_string()]
 		);
 	}
@@ -327,7 +1230,15 @@ mod match_words_tests {
 		];
 
 		assert_eq!(
-			match_words_from_tokens(&tokens, "t", "", "ytanpem", Some(&TEST_WORDS)).unwrap(),
+			match_words_from_tokens(
+				&tokens,
+				"t",
+				"",
+				"ytanpem",
+				WithinMode::Repeat,
+				Some(&TEST_WORDS)
+			)
+			.unwrap(),
 			vec!["yenta".to_string()]
 		);
 
@@ -341,9 +1252,388 @@ mod match_words_tests {
 		let test_words = [
 			"blast", "flats", "loath", "slant", "slats", "stalk", "stall", "trail", "trawl",
 		];
-		let result =
-			match_words_from_tokens(&tokens, "lat", "pesk", "", Some(&test_words)).unwrap();
+		let result = match_words_from_tokens(
+			&tokens,
+			"lat",
+			"pesk",
+			"",
+			WithinMode::Repeat,
+			Some(&test_words),
+		)
+		.unwrap();
 
 		assert_eq!(result, vec!["trail", "trawl"]);
 	}
+
+	#[test]
+	fn should_match_extended_patterns() {
+		let words = [
+			"bring", "brings", "cat", "cats", "coat", "rating", "string", "stings", "sting",
+		];
+		let matched = |pattern: &str| {
+			match_words(pattern, "", "", "", WithinMode::Repeat, Some(&words)).unwrap()
+		};
+
+		assert_eq!(matched("a-c ** \"ing\""), vec!["bring"]);
+		assert_eq!(
+			matched("** \"ing\" s?"),
+			vec!["bring", "brings", "rating", "string", "stings", "sting"]
+		);
+		assert_eq!(matched("c *{1,2} t"), vec!["cat", "coat"]);
+		assert_eq!(matched("c *? a t s?"), vec!["cat", "cats", "coat"]);
+		assert_eq!(matched("s t *{3,}"), vec!["string", "stings", "sting"]);
+		assert_eq!(matched("!a-r ** g"), vec!["string", "sting"]);
+	}
+
+	#[test]
+	fn should_match_classes_and_sets() {
+		let words = ["aloud", "audio", "jazzy", "quiet", "quiz", "squat", "trust"];
+		let mut sets = LetterSets::default();
+
+		sets.define("rare=jqxz").unwrap();
+
+		let matched = |pattern: &str, include: &str, exclude: &str| {
+			match_words_with_sets(
+				pattern,
+				include,
+				exclude,
+				"",
+				WithinMode::Repeat,
+				&sets,
+				Some(&words),
+			)
+			.unwrap()
+		};
+
+		assert_eq!(matched("C V **", "", ""), vec!["jazzy", "quiet", "quiz"]);
+		assert_eq!(matched("@rare **", "", ""), vec!["jazzy", "quiet", "quiz"]);
+		assert_eq!(matched("** !C", "", ""), vec!["audio"]);
+		assert_eq!(matched("**", "VVV", ""), vec!["aloud", "audio", "quiet"]);
+		assert_eq!(matched("**", "uVV", ""), vec!["aloud", "audio", "quiet"]);
+		assert_eq!(matched("**", "", "@rare"), vec!["aloud", "audio", "trust"]);
+		assert_eq!(matched("@end=yz ** @end", "", ""), vec!["jazzy", "quiz"]);
+		assert_eq!(matched("@q=q @q **", "C", "V"), Vec::<&str>::new());
+		assert_eq!(matched("@q=q @q **", "@rare", ""), vec!["quiet", "quiz"]);
+		assert_eq!(
+			match_words_with_sets(
+				"**",
+				"",
+				"@rare",
+				"",
+				WithinMode::Repeat,
+				&LetterSets::default(),
+				Some(&words)
+			)
+			.unwrap_err()
+			.to_string(),
+			"invalid letters @rare: unknown set @rare"
+		);
+	}
+
+	#[test]
+	fn should_reuse_compiled_matcher() {
+		let matcher = Matcher::new("* * * **", "d", "", "trubador", WithinMode::Rack).unwrap();
+		let words = ["abord", "adorb", "bread", "road", "tabor", "trod"];
+
+		assert!(matcher.is_match("road"));
+		assert!(!matcher.is_match("tabor"));
+		assert_eq!(
+			matcher.filter(words).collect::<Vec<_>>(),
+			vec!["abord", "adorb", "road", "trod"]
+		);
+		assert_eq!(matcher.count(words.iter().copied()), 4);
+
+		let shared = matcher.clone();
+		let count = std::thread::spawn(move || shared.count(["dart", "drab", "dread"]))
+			.join()
+			.unwrap();
+
+		assert_eq!(count, 2);
+		assert_eq!(matcher.count(["dart", "drab", "dread"]), 2);
+	}
+
+	#[test]
+	fn should_match_common_words() {
+		let frequencies: Arc<WordFrequencies> = Arc::new(
+			[("road", 30), ("trod", 4), ("abord", 1)]
+				.into_iter()
+				.collect(),
+		);
+		let matcher = Matcher::new("* * * **", "d", "", "trubador", WithinMode::Rack)
+			.unwrap()
+			.with_min_frequency(frequencies, 4);
+		let words = ["abord", "adorb", "bread", "road", "tabor", "trod"];
+
+		assert_eq!(matcher.find_all(&words), vec!["road", "trod"]);
+	}
+
+	#[test]
+	fn should_match_the_same_words_with_an_index() {
+		let dict = get_dictionary(&Dictionary::Gwicks).unwrap();
+		let copy: Vec<&str> = dict.to_vec();
+		let queries = [
+			("* r * nt !sy", "", "", ""),
+			("**", "ll", "e", ""),
+			("*{1,3} \"ing\"", "", "", ""),
+			("C V **", "VV", "", ""),
+			("* * * **", "d", "", "trubador"),
+		];
+
+		for (pattern, include, exclude, within) in queries {
+			let matched = |haystack: &[&'static str]| {
+				match_words(
+					pattern,
+					include,
+					exclude,
+					within,
+					WithinMode::Rack,
+					Some(haystack),
+				)
+				.unwrap()
+			};
+
+			assert_eq!(matched(dict), matched(&copy), "{pattern}");
+		}
+
+		assert!(DictionaryRegistry::global().index_of(dict).is_some());
+	}
+
+	#[test]
+	fn should_stream_matches() {
+		let dict = get_dictionary(&Dictionary::Gwicks).unwrap();
+		let all = match_words("* r * nt !sy", "", "", "", WithinMode::Rack, Some(dict)).unwrap();
+		let first: Vec<&str> =
+			match_words_iter("* r * nt !sy", "", "", "", WithinMode::Rack, Some(dict))
+				.unwrap()
+				.take(3)
+				.collect();
+
+		assert_eq!(first, all.get(..3).unwrap());
+		assert_eq!(
+			count_matches("* r * nt !sy", "", "", "", WithinMode::Rack, Some(dict)).unwrap(),
+			all.len()
+		);
+		assert_eq!(
+			count_matches("**", "", "", "", WithinMode::Rack, Some(&TEST_WORDS)).unwrap(),
+			TEST_WORDS.len()
+		);
+		assert!(match_words_iter("", "", "", "", WithinMode::Rack, None).is_err());
+	}
+
+	#[test]
+	fn should_require_repeated_include_chars() {
+		let tokens = vec![MatcherToken::MatchAnyChars];
+		let test_words = ["eerie", "elder", "enter", "tree"];
+
+		assert_eq!(
+			match_words_from_tokens(&tokens, "e", "", "", WithinMode::Rack, Some(&test_words))
+				.unwrap(),
+			test_words
+		);
+		assert_eq!(
+			match_words_from_tokens(&tokens, "ee", "", "", WithinMode::Rack, Some(&test_words))
+				.unwrap(),
+			vec!["eerie", "elder", "enter", "tree"]
+		);
+		assert_eq!(
+			match_words_from_tokens(&tokens, "eee", "", "", WithinMode::Rack, Some(&test_words))
+				.unwrap(),
+			vec!["eerie"]
+		);
+		assert_eq!(
+			match_words_from_tokens(&tokens, "eer", "", "", WithinMode::Rack, Some(&test_words))
+				.unwrap(),
+			vec!["eerie", "elder", "enter", "tree"]
+		);
+	}
+
+	#[test]
+	fn should_limit_within_chars_to_rack_count() {
+		let tokens = vec![MatcherToken::MatchAnyChars];
+		let test_words = ["three", "tree", "teeth", "the", "toe", "word"];
+
+		assert_eq!(
+			match_words_from_tokens(
+				&tokens,
+				"",
+				"",
+				"theword",
+				WithinMode::Repeat,
+				Some(&test_words)
+			)
+			.unwrap(),
+			vec!["three", "tree", "teeth", "the", "toe", "word"]
+		);
+		assert_eq!(
+			match_words_from_tokens(
+				&tokens,
+				"",
+				"",
+				"theword",
+				WithinMode::Rack,
+				Some(&test_words)
+			)
+			.unwrap(),
+			vec!["the", "toe", "word"]
+		);
+		assert_eq!(
+			match_words_from_tokens(
+				&tokens,
+				"",
+				"",
+				"theewordt",
+				WithinMode::Rack,
+				Some(&test_words)
+			)
+			.unwrap(),
+			vec!["three", "tree", "teeth", "the", "toe", "word"]
+		);
+	}
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod explain_tests {
+	use super::*;
+
+	#[test]
+	fn should_explain_failed_letters() {
+		let matcher = Matcher::new("* * * * *", "rn", "s", "", WithinMode::Repeat).unwrap();
+
+		assert_eq!(matcher.explain("crane"), vec![]);
+		assert_eq!(
+			matcher.explain("slate"),
+			vec![
+				FailedConstraint::Excluded('s'),
+				FailedConstraint::Missing {
+					letter: 'r',
+					count: 1
+				},
+				FailedConstraint::Missing {
+					letter: 'n',
+					count: 1
+				},
+			]
+		);
+
+		let matcher = Matcher::new("**", "ee", "", "treeh", WithinMode::Rack).unwrap();
+
+		assert_eq!(
+			matcher.explain("terre"),
+			vec![FailedConstraint::NotWithin('r')]
+		);
+		assert_eq!(matcher.explain("tree"), vec![]);
+		assert_eq!(
+			matcher.explain("the"),
+			vec![FailedConstraint::Missing {
+				letter: 'e',
+				count: 2
+			}]
+		);
+	}
+
+	#[test]
+	fn should_explain_failed_positions() {
+		let matcher = Matcher::new("c !r a * \"es\"", "", "", "", WithinMode::Repeat).unwrap();
+
+		assert_eq!(
+			matcher.explain("grapes"),
+			vec![
+				FailedConstraint::Position {
+					position: 0,
+					token: MatcherToken::MatchAnyCharIn("c".to_string()),
+					letters: "g".to_string(),
+				},
+				FailedConstraint::Position {
+					position: 1,
+					token: MatcherToken::ExcludeAllCharsIn("r".to_string()),
+					letters: "r".to_string(),
+				},
+			]
+		);
+		assert_eq!(
+			matcher.explain("crane"),
+			vec![FailedConstraint::Length {
+				length: 5,
+				min: 6,
+				max: Some(6)
+			}]
+		);
+		assert_eq!(
+			matcher.explain("grapes").first().unwrap().to_string(),
+			"has g at position 1, the pattern needs c"
+		);
+
+		let matcher = Matcher::new("c ** \"ra\" ** * g", "", "", "", WithinMode::Repeat).unwrap();
+
+		assert_eq!(
+			matcher.explain("string"),
+			vec![FailedConstraint::Position {
+				position: 0,
+				token: MatcherToken::MatchAnyCharIn("c".to_string()),
+				letters: "s".to_string(),
+			}]
+		);
+		assert_eq!(
+			matcher.explain("crisp"),
+			vec![FailedConstraint::Position {
+				position: 4,
+				token: MatcherToken::MatchAnyCharIn("g".to_string()),
+				letters: "p".to_string(),
+			}]
+		);
+		assert_eq!(matcher.explain("cling"), vec![FailedConstraint::Pattern]);
+		assert_eq!(matcher.explain("cragging"), vec![]);
+	}
+
+	#[test]
+	fn should_format_tokens_as_pattern() {
+		let tokens = parse_pattern("** * abc !def \"ing\" *? a{2} b{2,} c{1,3}").unwrap();
+
+		assert_eq!(
+			format_pattern(&tokens),
+			"** * abc !def \"ing\" *? a{2} b{2,} c{1,3}"
+		);
+	}
+
+	#[test]
+	fn should_parse_formatted_pattern() {
+		let tokens = parse_pattern("@rare=jqxz a-c V? !@rare{2,} \"ing\"").unwrap();
+		let pattern = format_pattern(&tokens);
+
+		assert_eq!(pattern, "abc aeiou? !jqxz{2,} \"ing\"");
+		// tokens compare loosely, e.g. `*` equals `**`, so compare formatted
+		assert_eq!(format_pattern(&parse_pattern(&pattern).unwrap()), pattern);
+		assert!(matches!(
+			"!jqxz{2,}".parse::<MatcherToken>().unwrap(),
+			MatcherToken::Repeated { token, min: 2, max: None }
+				if matches!(token.as_ref(), MatcherToken::ExcludeAllCharsIn(chars) if chars == "jqxz")
+		));
+		assert!("a b".parse::<MatcherToken>().is_err());
+	}
+}
+
+#[cfg(all(test, feature = "serde"))]
+#[allow(clippy::unwrap_used)]
+mod serde_tests {
+	use super::*;
+
+	#[test]
+	fn should_serialize_tokens_as_pattern_parts() {
+		let tokens = parse_pattern("** s? !ae \"ing\"").unwrap();
+		let json = serde_json::to_string(&tokens).unwrap();
+
+		assert_eq!(json, r#"["**","s?","!ae","\"ing\""]"#);
+		assert_eq!(
+			format_pattern(&serde_json::from_str::<Vec<MatcherToken>>(&json).unwrap()),
+			format_pattern(&tokens)
+		);
+	}
+
+	#[test]
+	fn should_error_on_invalid_pattern_part() {
+		let err = serde_json::from_str::<MatcherToken>(r#""a*""#).unwrap_err();
+
+		assert!(err.to_string().starts_with("invalid pattern a*"));
+	}
 }
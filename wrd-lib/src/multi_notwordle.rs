@@ -0,0 +1,363 @@
+use crate::data::{Dictionary, WordFrequencies, get_dictionary};
+use crate::notwordle::{GuessResultToken, Notwordle, NotwordleError, tokenize_guess_result};
+use crate::solver::{GuessPool, Suggestion, rank_guesses_across_boards_weighted};
+
+/// Notwordle for multi-board variants like Dordle and Quordle, where every
+/// guess is played on all boards at once. Each board is refined on its own.
+#[derive(Clone, Debug, Default)]
+pub struct MultiNotwordle {
+	boards: Vec<Notwordle>,
+	guesses: Vec<String>,
+}
+
+impl MultiNotwordle {
+	#[must_use]
+	pub fn new(board_count: usize) -> Self {
+		Self {
+			boards: (0..board_count).map(|_| Notwordle::default()).collect(),
+			guesses: vec![],
+		}
+	}
+
+	#[must_use]
+	pub const fn board_count(&self) -> usize {
+		self.boards.len()
+	}
+
+	/// Guessed words, in order
+	#[must_use]
+	pub fn guesses(&self) -> &[String] {
+		&self.guesses
+	}
+
+	/// Whether each board has been solved
+	#[must_use]
+	pub fn solved(&self) -> Vec<bool> {
+		self.boards.iter().map(Notwordle::is_solved).collect()
+	}
+
+	/// Register one guess as a `|` separated list of results, one per board,
+	/// in any format `Notwordle::register_guess_result` accepts. Results for
+	/// solved boards are left empty.
+	///
+	/// If the first result has a word, e.g. `crane:g....`, the following
+	/// results can be colours alone: `crane:g....|.y...|..yy.`.
+	///
+	/// Returns the tokenized results, `None` for boards already solved.
+	///
+	/// # Errors
+	/// See `register_guess_results`.
+	pub fn register_guess_row(
+		&mut self,
+		row: &str,
+	) -> Result<Vec<Option<Vec<GuessResultToken>>>, NotwordleError> {
+		let entries: Vec<&str> = row.split('|').map(str::trim).collect();
+		let word = entries
+			.first()
+			.and_then(|entry| entry.split_once(':'))
+			.map(|(word, _)| word.trim());
+		let results: Vec<String> = entries
+			.iter()
+			.map(|entry| match word {
+				Some(word) if !entry.is_empty() && !entry.contains(':') => {
+					format!("{word}:{entry}")
+				}
+				_ => (*entry).to_string(),
+			})
+			.collect();
+		let results: Vec<&str> = results.iter().map(String::as_str).collect();
+
+		self.register_guess_results(&results)
+	}
+
+	/// Register one guess given as a result for each board. Nothing is
+	/// registered if any result is invalid.
+	///
+	/// # Errors
+	/// Errors if the number of results does not match the number of boards, a
+	/// result is missing for an unsolved board or given for a solved one, or
+	/// results spell different words. Propagates errors from tokenizing and
+	/// registering results.
+	pub fn register_guess_results(
+		&mut self,
+		results: &[&str],
+	) -> Result<Vec<Option<Vec<GuessResultToken>>>, NotwordleError> {
+		if results.len() != self.boards.len() {
+			return Err(NotwordleError::WrongBoardCount {
+				expected: self.boards.len(),
+				got: results.len(),
+			});
+		}
+
+		let mut tokenized: Vec<Option<Vec<GuessResultToken>>> = vec![];
+
+		for (board, (notwordle, result)) in self.boards.iter().zip(results).enumerate() {
+			let is_empty = result.trim().is_empty();
+
+			match (notwordle.is_solved(), is_empty) {
+				(true, true) => tokenized.push(None),
+				(true, false) => {
+					return Err(NotwordleError::SolvedBoardResult(board.saturating_add(1)));
+				}
+				(false, true) => {
+					return Err(NotwordleError::MissingBoardResult(board.saturating_add(1)));
+				}
+				(false, false) => tokenized.push(Some(tokenize_guess_result(result)?)),
+			}
+		}
+
+		let words: Vec<String> = tokenized.iter().flatten().map(|t| spell(t)).collect();
+		let Some(guess) = words.first() else {
+			return Err(NotwordleError::NoGuessResults);
+		};
+
+		if let Some(other) = words.iter().find(|word| *word != guess) {
+			return Err(NotwordleError::MismatchedBoardGuesses {
+				guess: guess.clone(),
+				other: other.clone(),
+			});
+		}
+
+		if let Some(previous) = self.guesses.last() {
+			let (current, previous) = (guess.chars().count(), previous.chars().count());
+
+			if current != previous {
+				return Err(NotwordleError::InvalidGuessResultLength { current, previous });
+			}
+		}
+
+		// check every board first so a bad result leaves none registered
+		for (notwordle, tokens) in self.boards.iter().zip(&tokenized) {
+			if let Some(tokens) = tokens {
+				notwordle.check_guess_tokens(notwordle.guesses().len(), tokens)?;
+			}
+		}
+
+		for (notwordle, tokens) in self.boards.iter_mut().zip(&tokenized) {
+			if let Some(tokens) = tokens {
+				notwordle.register_guess_tokens(tokens.clone())?;
+			}
+		}
+
+		self.guesses.push(guess.clone());
+
+		Ok(tokenized)
+	}
+
+	/// Remove the last guess from every board that took a result for it,
+	/// returning the guessed word.
+	pub fn undo_guess(&mut self) -> Option<String> {
+		let guess = self.guesses.pop()?;
+		let row = self.guesses.len();
+
+		for notwordle in &mut self.boards {
+			// boards solved before the guess took no result for it
+			if notwordle.guesses().len() > row {
+				let _ = notwordle.remove_guess(row);
+			}
+		}
+
+		Some(guess)
+	}
+
+	/// Words remaining on each board
+	///
+	/// # Errors
+	/// Propagates errors from `match_words_from_tokens`.
+	pub fn refine(
+		&self,
+		words: Option<&[&'static str]>,
+	) -> Result<Vec<Vec<&'static str>>, NotwordleError> {
+		self.boards
+			.iter()
+			.map(|notwordle| notwordle.remaining(words).map_err(NotwordleError::from))
+			.collect()
+	}
+
+	/// Suggest the `limit` next guesses expected to tell the most about all
+	/// unsolved boards together. A `GuessPool::Remaining` pool is every word
+	/// still possible on any unsolved board. Common answers count for more
+	/// when `frequencies` are given.
+	///
+	/// # Errors
+	/// Errors if no guesses have been registered.
+	/// Propagates errors from `match_words_from_tokens`.
+	pub fn suggest(
+		&self,
+		words: Option<&[&'static str]>,
+		frequencies: Option<&WordFrequencies>,
+		pool: GuessPool,
+		limit: usize,
+	) -> Result<Vec<Suggestion>, NotwordleError> {
+		let word_len = self
+			.guesses
+			.first()
+			.map(|guess| guess.chars().count())
+			.ok_or(NotwordleError::NoGuessResults)?;
+		let words = words.unwrap_or_else(|| get_dictionary(&Dictionary::Moby).unwrap_or_default());
+		let unsolved: Vec<Vec<&'static str>> = self
+			.refine(Some(words))?
+			.into_iter()
+			.zip(self.solved())
+			.filter(|(_, solved)| !solved)
+			.map(|(remaining, _)| remaining)
+			.collect();
+		let guesses: Vec<&'static str> = match pool {
+			GuessPool::Remaining => words
+				.iter()
+				.filter(|word| unsolved.iter().any(|remaining| remaining.contains(word)))
+				.copied()
+				.collect(),
+			GuessPool::Dictionary => words
+				.iter()
+				.filter(|word| word.chars().count() == word_len)
+				.copied()
+				.collect(),
+		};
+
+		Ok(rank_guesses_across_boards_weighted(
+			&guesses,
+			&unsolved,
+			frequencies,
+			limit,
+		))
+	}
+}
+
+fn spell(tokens: &[GuessResultToken]) -> String {
+	tokens
+		.iter()
+		.map(|token| {
+			let (GuessResultToken::Right(c)
+			| GuessResultToken::Wrong(c)
+			| GuessResultToken::WrongPosition(c)) = token;
+
+			c.as_str()
+		})
+		.collect()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+	use super::*;
+
+	static WORDS: [&str; 8] = [
+		"batch", "blimp", "climb", "hatch", "latch", "match", "patch", "watch",
+	];
+
+	#[test]
+	fn should_refine_boards_independently() {
+		let mut boards = MultiNotwordle::new(2);
+
+		// answers are latch and climb
+		boards.register_guess_row("match:.gggg|y..y.").unwrap();
+
+		assert_eq!(
+			boards.refine(Some(&WORDS)).unwrap(),
+			vec![
+				vec!["batch", "hatch", "latch", "patch", "watch"],
+				vec!["climb"]
+			]
+		);
+		assert_eq!(boards.solved(), vec![false, false]);
+
+		boards.register_guess_row("climb:yy...|ggggg").unwrap();
+
+		assert_eq!(boards.solved(), vec![false, true]);
+		assert_eq!(
+			boards.refine(Some(&WORDS)).unwrap().first().unwrap(),
+			&vec!["latch"]
+		);
+
+		// solved boards take no more results
+		let tokenized = boards.register_guess_row("latch:ggggg|").unwrap();
+
+		assert!(tokenized.get(1).unwrap().is_none());
+		assert_eq!(boards.solved(), vec![true, true]);
+		assert_eq!(boards.guesses(), &["match", "climb", "latch"]);
+	}
+
+	#[test]
+	fn should_undo_the_last_guess() {
+		let mut boards = MultiNotwordle::new(2);
+
+		boards.register_guess_row("climb:yy...|ggggg").unwrap();
+		boards.register_guess_row("latch:ggggg|").unwrap();
+
+		assert_eq!(boards.undo_guess(), Some("latch".to_string()));
+		assert_eq!(boards.solved(), vec![false, true]);
+		assert_eq!(boards.guesses(), &["climb"]);
+
+		assert_eq!(boards.undo_guess(), Some("climb".to_string()));
+		assert!(boards.boards.iter().all(|board| board.guesses().len() == 0));
+		assert_eq!(boards.undo_guess(), None);
+	}
+
+	#[test]
+	fn should_suggest_across_unsolved_boards() {
+		let mut boards = MultiNotwordle::new(2);
+
+		boards.register_guess_row("watch:.gggg|...y.").unwrap();
+
+		let suggestions = boards
+			.suggest(Some(&WORDS), None, GuessPool::Dictionary, 2)
+			.unwrap();
+
+		assert_eq!(
+			suggestions.iter().map(|s| s.word).collect::<Vec<_>>(),
+			vec!["blimp", "climb"]
+		);
+
+		let suggestions = boards
+			.suggest(Some(&WORDS), None, GuessPool::Remaining, 10)
+			.unwrap();
+
+		assert!(suggestions.iter().all(|s| s.is_candidate));
+		assert!(!suggestions.iter().any(|s| s.word == "watch"));
+	}
+
+	#[test]
+	fn should_error_on_invalid_rows() {
+		let mut boards = MultiNotwordle::new(2);
+
+		assert_eq!(
+			boards.register_guess_row("match:.gggg").unwrap_err(),
+			NotwordleError::WrongBoardCount {
+				expected: 2,
+				got: 1
+			}
+		);
+		assert_eq!(
+			boards.register_guess_row("match:.gggg|").unwrap_err(),
+			NotwordleError::MissingBoardResult(2)
+		);
+		assert_eq!(
+			boards
+				.register_guess_row("match:.gggg|latch:.....")
+				.unwrap_err(),
+			NotwordleError::MismatchedBoardGuesses {
+				guess: "match".to_string(),
+				other: "latch".to_string()
+			}
+		);
+		assert!(boards.guesses().is_empty());
+		boards.register_guess_row("match:.gggg|y..y.").unwrap();
+		assert_eq!(boards.guesses(), &["match"]);
+		// the second board already ruled out a
+		assert!(matches!(
+			boards.register_guess_row("latch:.gggg|.g...").unwrap_err(),
+			NotwordleError::Contradiction(_)
+		));
+		assert!(boards.boards.iter().all(|board| board.guesses().len() == 1));
+
+		boards.register_guess_row("climb:y....|ggggg").unwrap();
+
+		assert_eq!(
+			boards
+				.register_guess_row("latch:ggggg|latch:.....")
+				.unwrap_err(),
+			NotwordleError::SolvedBoardResult(2)
+		);
+	}
+}
@@ -1,14 +1,20 @@
+use std::fmt;
+use std::str::FromStr;
 use std::sync::LazyLock;
 
 use regex::Regex;
 
-use crate::match_words::{MatcherToken, match_words_from_tokens};
+use crate::data::{Dictionary, WordFrequencies, get_dictionary};
+use crate::match_words::{
+	FailedConstraint, MatchWordsError, Matcher, MatcherToken, WithinMode, match_words_from_tokens,
+};
+use crate::solver::{GuessPool, Suggestion, rank_guesses_weighted};
 use crate::util::{non_empty_str, unique_string};
 
 #[derive(thiserror::Error, Debug, PartialEq, Eq)]
 pub enum NotwordleError {
 	#[error("could not get remaining words: {0}")]
-	FailedToMatch(#[from] crate::match_words::MatchWordsError),
+	FailedToMatch(#[from] MatchWordsError),
 	#[error(
 		"all guess results must have the same number of entries, got {current}, previous had {previous}"
 	)]
@@ -17,9 +23,110 @@ pub enum NotwordleError {
 	InvalidGuessResultEntry(String),
 	#[error("no characters to match in entry: {0}")]
 	GuessResultEntryNeedsChar(String),
+	#[error("at least one guess result is needed")]
+	NoGuessResults,
+	#[error("no guess result {index}, there are {len}")]
+	GuessIndexOutOfRange { index: usize, len: usize },
+	#[error("guess has {guess} characters but answer has {answer}")]
+	MismatchedGuessLength { guess: usize, answer: usize },
+	#[error("invalid guess word: {0}, expected letters a-z")]
+	InvalidGuessWord(String),
+	#[error(
+		"invalid colours: {0}, expected g (right), y (wrong position) or . (not in word) for each letter"
+	)]
+	InvalidColourCode(String),
+	#[error("{word} has {letters} letters but {colours} colours")]
+	MismatchedColourCode {
+		word: String,
+		letters: usize,
+		colours: usize,
+	},
+	#[error(
+		"invalid shorthand: {0}, expected uppercase (right), ? + letter (wrong position) or lowercase (not in word)"
+	)]
+	InvalidShorthand(String),
+	#[error("expected a guess result for each of {expected} boards, got {got}")]
+	WrongBoardCount { expected: usize, got: usize },
+	#[error("missing guess result for board {0}")]
+	MissingBoardResult(usize),
+	#[error("board {0} is already solved, leave its result empty")]
+	SolvedBoardResult(usize),
+	#[error("every board must have the same guess, got {guess} and {other}")]
+	MismatchedBoardGuesses { guess: String, other: String },
+	#[error("contradictory guess results: {0}")]
+	Contradiction(#[from] Contradiction),
+}
+
+/// Guess results that no word can satisfy, pointing at the entries that
+/// disagree
+#[derive(thiserror::Error, Clone, Debug, PartialEq, Eq)]
+pub enum Contradiction {
+	#[error("{letter} at {tile} and {other_letter} at {other} can not both be right")]
+	PositionTaken {
+		letter: String,
+		tile: GuessTile,
+		other_letter: String,
+		other: GuessTile,
+	},
+	#[error("{letter} is right at {tile} but not in that position at {other}")]
+	RightAndMisplaced {
+		letter: String,
+		tile: GuessTile,
+		other: GuessTile,
+	},
+	#[error("{present} means at least {min} {letter} in the word but {absent} means at most {max}")]
+	LetterCount {
+		letter: String,
+		min: usize,
+		present: GuessTile,
+		max: usize,
+		absent: GuessTile,
+	},
+	#[error("the guess results need at least {needed} letters but the word has {length}")]
+	TooManyLetters {
+		needed: usize,
+		length: usize,
+		tiles: Vec<GuessTile>,
+	},
+}
+
+impl Contradiction {
+	/// The entries that disagree
+	#[must_use]
+	pub fn tiles(&self) -> Vec<GuessTile> {
+		match self {
+			Self::PositionTaken { tile, other, .. }
+			| Self::RightAndMisplaced { tile, other, .. } => {
+				vec![*tile, *other]
+			}
+			Self::LetterCount {
+				present, absent, ..
+			} => vec![*present, *absent],
+			Self::TooManyLetters { tiles, .. } => tiles.clone(),
+		}
+	}
+}
+
+/// An entry of a guess result, by the index of its guess result and its
+/// position in it. Both count from 0 but display from 1.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GuessTile {
+	pub row: usize,
+	pub position: usize,
+}
+
+impl fmt::Display for GuessTile {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(
+			f,
+			"row {} position {}",
+			self.row.saturating_add(1),
+			self.position.saturating_add(1)
+		)
+	}
 }
 
-#[derive(Default)]
+#[derive(Clone, Debug, Default)]
 pub struct Notwordle {
 	guess_results: Vec<Vec<GuessResultToken>>,
 }
@@ -31,9 +138,94 @@ pub enum GuessResultToken {
 	WrongPosition(String),
 }
 
+/// Formats as a guess result entry, e.g. `p`, `?l` or `!a`
+impl fmt::Display for GuessResultToken {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Right(c) => write!(f, "{c}"),
+			Self::WrongPosition(c) => write!(f, "?{c}"),
+			Self::Wrong(c) => write!(f, "!{c}"),
+		}
+	}
+}
+
+/// Parses a single guess result entry, e.g. `p`, `?l` or `!a`
+impl FromStr for GuessResultToken {
+	type Err = NotwordleError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match tokenize_entries(s)?.as_slice() {
+			[token] => Ok(token.clone()),
+			_ => Err(NotwordleError::InvalidGuessResultEntry(s.to_string())),
+		}
+	}
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for GuessResultToken {
+	fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.collect_str(self)
+	}
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for GuessResultToken {
+	fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		let entry = <String as serde::Deserialize>::deserialize(deserializer)?;
+
+		entry.parse().map_err(serde::de::Error::custom)
+	}
+}
+
+/// The guess results of a `Notwordle` as they are saved, one row of entries
+/// per guess, e.g. `{"guesses": ["p ?l !a t !e"]}`
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SavedNotwordle {
+	guesses: Vec<String>,
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Notwordle {
+	fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		let saved = SavedNotwordle {
+			guesses: self.guesses().map(format_guess_result).collect(),
+		};
+
+		saved.serialize(serializer)
+	}
+}
+
+/// Registers the saved rows in order, so rows of different lengths or that
+/// contradict each other are rejected as they would be when entered
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Notwordle {
+	fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		let saved = SavedNotwordle::deserialize(deserializer)?;
+		let mut notwordle = Self::default();
+
+		for (index, row) in saved.guesses.iter().enumerate() {
+			notwordle.register_guess_result(row).map_err(|err| {
+				serde::de::Error::custom(format!("guess {}: {err}", index.saturating_add(1)))
+			})?;
+		}
+
+		Ok(notwordle)
+	}
+}
+
 impl Notwordle {
+	/// Register a guess result written in any of these formats:
+	/// - entries: `p ?l !a t !e`
+	/// - word and colours: `plate:gy.g.`, where `b`, `x` or `-` also mean
+	///   not in word
+	/// - word and emoji, as pasted from a shared result: `plate 🟩🟨⬛🟩⬛`
+	/// - case shorthand: `P?laTe`, uppercase right, `?` before a letter in
+	///   the wrong position, lowercase not in word
+	///
 	/// # Errors
-	/// Errors if guess result token count does not match previous entries.
+	/// Errors if guess result token count does not match previous entries, or
+	/// no word could satisfy it together with them.
 	/// Propagates errors from `tokenize_guess_result`.
 	pub fn register_guess_result(
 		&mut self,
@@ -41,29 +233,230 @@ impl Notwordle {
 	) -> Result<Vec<GuessResultToken>, NotwordleError> {
 		let tokenized = tokenize_guess_result(result)?;
 
-		if let Some(stored) = self.guess_results.last() {
-			let stored_len = stored.len();
-			let new_len = tokenized.len();
+		self.register_guess_tokens(tokenized.clone())?;
 
-			if stored_len != new_len {
-				return Err(NotwordleError::InvalidGuessResultLength {
-					current: new_len,
-					previous: stored_len,
-				});
-			}
+		Ok(tokenized)
+	}
+
+	/// Register an already tokenized guess result, e.g. from `score_guess`.
+	///
+	/// # Errors
+	/// Errors if guess result token count does not match previous entries, or
+	/// no word could satisfy it together with them.
+	pub fn register_guess_tokens(
+		&mut self,
+		tokens: Vec<GuessResultToken>,
+	) -> Result<(), NotwordleError> {
+		self.check_guess_tokens(self.guess_results.len(), &tokens)?;
+		self.guess_results.push(tokens);
+
+		Ok(())
+	}
+
+	/// Check `tokens` can be stored at `index`, replacing the guess result
+	/// there, or after the last one when `index` is past the end.
+	pub(crate) fn check_guess_tokens(
+		&self,
+		index: usize,
+		tokens: &[GuessResultToken],
+	) -> Result<(), NotwordleError> {
+		let other_len = self
+			.guess_results
+			.iter()
+			.enumerate()
+			.find(|(i, _)| *i != index)
+			.map(|(_, other)| other.len());
+
+		if let Some(previous) = other_len
+			&& previous != tokens.len()
+		{
+			return Err(NotwordleError::InvalidGuessResultLength {
+				current: tokens.len(),
+				previous,
+			});
 		}
 
-		self.guess_results.push(tokenized.clone());
+		let mut results = self.guess_results.clone();
+
+		if let Some(stored) = results.get_mut(index) {
+			*stored = tokens.to_vec();
+		} else {
+			results.push(tokens.to_vec());
+		}
+
+		find_contradiction(&results).map_or(Ok(()), |contradiction| Err(contradiction.into()))
+	}
+
+	/// Remove the guess result at `index`, returning its tokens.
+	///
+	/// # Errors
+	/// Errors if there is no guess result at `index`.
+	pub fn remove_guess(&mut self, index: usize) -> Result<Vec<GuessResultToken>, NotwordleError> {
+		self.check_index(index)?;
+
+		Ok(self.guess_results.remove(index))
+	}
+
+	/// Replace the guess result at `index`, e.g. to fix a mistyped entry. Takes
+	/// the same formats as `register_guess_result`.
+	///
+	/// # Errors
+	/// Errors if there is no guess result at `index`, the token count does not
+	/// match the other entries, or no word could satisfy it together with them.
+	/// Propagates errors from `tokenize_guess_result`.
+	pub fn replace_guess(
+		&mut self,
+		index: usize,
+		result: &str,
+	) -> Result<Vec<GuessResultToken>, NotwordleError> {
+		self.check_index(index)?;
+
+		let tokenized = tokenize_guess_result(result)?;
+
+		self.check_guess_tokens(index, &tokenized)?;
+
+		if let Some(stored) = self.guess_results.get_mut(index) {
+			stored.clone_from(&tokenized);
+		}
 
 		Ok(tokenized)
 	}
 
+	/// Remove every guess result
+	pub fn clear(&mut self) {
+		self.guess_results.clear();
+	}
+
+	/// The registered guess results, in the order they were registered
+	pub fn guesses(&self) -> impl ExactSizeIterator<Item = &[GuessResultToken]> {
+		self.guess_results.iter().map(Vec::as_slice)
+	}
+
+	const fn check_index(&self, index: usize) -> Result<(), NotwordleError> {
+		let len = self.guess_results.len();
+
+		if index < len {
+			Ok(())
+		} else {
+			Err(NotwordleError::GuessIndexOutOfRange { index, len })
+		}
+	}
+
+	/// Whether the last registered guess was right in every position
+	#[must_use]
+	pub fn is_solved(&self) -> bool {
+		self.guess_results.last().is_some_and(|result| {
+			result
+				.iter()
+				.all(|token| matches!(token, GuessResultToken::Right(_)))
+		})
+	}
+
 	/// # Errors
 	/// Propagates errors from `match_words_from_tokens`.
 	pub fn refine(&self, words: Option<&[&'static str]>) -> Result<Vec<&str>, String> {
+		self.remaining(words).map_err(|e| e.to_string())
+	}
+
+	/// Suggest the `limit` most informative next guesses, taken from `pool`.
+	/// Common answers count for more when `frequencies` are given.
+	///
+	/// # Errors
+	/// Errors if no guess results have been registered.
+	/// Propagates errors from `match_words_from_tokens`.
+	pub fn suggest(
+		&self,
+		words: Option<&[&'static str]>,
+		frequencies: Option<&WordFrequencies>,
+		pool: GuessPool,
+		limit: usize,
+	) -> Result<Vec<Suggestion>, NotwordleError> {
+		let word_len = self
+			.guess_results
+			.first()
+			.map(Vec::len)
+			.ok_or(NotwordleError::NoGuessResults)?;
+		let words = words.unwrap_or_else(|| get_dictionary(&Dictionary::Moby).unwrap_or_default());
+		let remaining = self.remaining(Some(words))?;
+		let guesses: Vec<&'static str> = match pool {
+			GuessPool::Remaining => remaining.clone(),
+			GuessPool::Dictionary => words
+				.iter()
+				.filter(|word| word.chars().count() == word_len)
+				.copied()
+				.collect(),
+		};
+
+		Ok(rank_guesses_weighted(
+			&guesses,
+			&remaining,
+			frequencies,
+			limit,
+		))
+	}
+
+	/// Every constraint from the guess results `word` fails, empty when it is
+	/// one of the remaining words.
+	///
+	/// # Errors
+	/// Errors if no guess results have been registered.
+	/// Propagates errors from `Matcher::from_tokens`.
+	pub fn explain(&self, word: &str) -> Result<Vec<FailedConstraint>, NotwordleError> {
+		if self.guess_results.is_empty() {
+			return Err(NotwordleError::NoGuessResults);
+		}
+
+		let (tokens, include, exclude) = get_match_args_from_results(&self.guess_results);
+		let matcher = Matcher::from_tokens(&tokens, &include, &exclude, "", WithinMode::Repeat)?;
+		let mut failed = matcher.explain(word);
+
+		for bound in get_letter_bounds_from_results(&self.guess_results) {
+			let count = word.chars().filter(|c| *c == bound.letter).count();
+			// the matcher explains missing and excluded letters
+			let is_explained = count == 0 || (bound.min == 0 && bound.max == Some(0));
+
+			if !bound.allows(word) && !is_explained {
+				failed.push(FailedConstraint::LetterCount {
+					letter: bound.letter,
+					count,
+					min: bound.min,
+					max: bound.max,
+				});
+			}
+		}
+
+		Ok(failed)
+	}
+
+	pub(crate) fn remaining(
+		&self,
+		words: Option<&[&'static str]>,
+	) -> Result<Vec<&'static str>, MatchWordsError> {
 		let (tokens, include, exclude) = get_match_args_from_results(&self.guess_results);
+		let bounds = get_letter_bounds_from_results(&self.guess_results);
+		let matched =
+			match_words_from_tokens(&tokens, &include, &exclude, "", WithinMode::Repeat, words)?;
 
-		match_words_from_tokens(&tokens, &include, &exclude, "", words).map_err(|e| e.to_string())
+		Ok(matched
+			.into_iter()
+			.filter(|word| bounds.iter().all(|bound| bound.allows(word)))
+			.collect())
+	}
+}
+
+/// How many times a letter can appear in the answer, as implied by guess results
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct LetterBound {
+	letter: char,
+	min: usize,
+	max: Option<usize>,
+}
+
+impl LetterBound {
+	fn allows(&self, word: &str) -> bool {
+		let count = word.chars().filter(|c| *c == self.letter).count();
+
+		count >= self.min && self.max.is_none_or(|max| count <= max)
 	}
 }
 
@@ -71,7 +464,42 @@ impl Notwordle {
 static GUESS_TOKEN_REGEX: LazyLock<Regex> =
 	LazyLock::new(|| Regex::new(r"^([!?])?([a-z])$").expect("invalid guess regex"));
 
-fn tokenize_guess_result(input: &str) -> Result<Vec<GuessResultToken>, NotwordleError> {
+/// Tokenize a guess result without registering it. Takes the formats
+/// `Notwordle::register_guess_result` does.
+///
+/// # Errors
+/// Errors if the input is not a guess result in any of the formats.
+pub fn tokenize_guess_result(input: &str) -> Result<Vec<GuessResultToken>, NotwordleError> {
+	let input = input.trim();
+
+	if let Some((word, colours)) = input.split_once(':') {
+		return tokenize_colour_code(word.trim(), colours.trim());
+	}
+
+	if let Some((word, colours)) = split_emoji_row(input) {
+		return tokenize_colour_code(word, colours);
+	}
+
+	// a single entry like `p` or `?p` is a one letter guess, not shorthand
+	if !input.is_empty() && !input.contains(' ') && !GUESS_TOKEN_REGEX.is_match(input) {
+		return tokenize_case_shorthand(input);
+	}
+
+	tokenize_entries(input)
+}
+
+/// Write a guess result as space separated entries, e.g. `p ?l !a t !e`,
+/// which `tokenize_guess_result` reads as the same tokens
+#[must_use]
+pub fn format_guess_result(tokens: &[GuessResultToken]) -> String {
+	tokens
+		.iter()
+		.map(ToString::to_string)
+		.collect::<Vec<_>>()
+		.join(" ")
+}
+
+fn tokenize_entries(input: &str) -> Result<Vec<GuessResultToken>, NotwordleError> {
 	let entries: Vec<_> = input.split(' ').filter_map(non_empty_str).collect();
 	let mut result: Vec<GuessResultToken> = vec![];
 
@@ -97,26 +525,324 @@ fn tokenize_guess_result(input: &str) -> Result<Vec<GuessResultToken>, Notwordle
 	Ok(result)
 }
 
-fn get_match_args_from_results(
-	guess_results: &[Vec<GuessResultToken>],
-) -> (Vec<MatcherToken>, String, String) {
-	let mut include = String::new();
-	let mut exclude = String::new();
-	let mut match_tokens: Vec<MatcherToken> = vec![];
+fn tokenize_colour_code(
+	word: &str,
+	colours: &str,
+) -> Result<Vec<GuessResultToken>, NotwordleError> {
+	let word = word.to_lowercase();
+
+	if word.is_empty() || !word.chars().all(|c| c.is_ascii_lowercase()) {
+		return Err(NotwordleError::InvalidGuessWord(word));
+	}
+
+	let marks: Vec<char> = colours.chars().filter(|c| *c != EMOJI_VARIATION).collect();
+	let letters = word.chars().count();
+
+	if marks.len() != letters {
+		return Err(NotwordleError::MismatchedColourCode {
+			word,
+			letters,
+			colours: marks.len(),
+		});
+	}
+
+	word.chars()
+		.zip(marks)
+		.map(|(letter, mark)| {
+			let letter = letter.to_string();
+
+			match mark.to_ascii_lowercase() {
+				'g' | '🟩' | '🟧' => Ok(GuessResultToken::Right(letter)),
+				'y' | '🟨' | '🟦' => Ok(GuessResultToken::WrongPosition(letter)),
+				'.' | 'b' | 'x' | '-' | '⬛' | '⬜' => Ok(GuessResultToken::Wrong(letter)),
+				_ => Err(NotwordleError::InvalidColourCode(colours.to_string())),
+			}
+		})
+		.collect()
+}
+
+// some platforms follow square emoji with a variation selector
+const EMOJI_VARIATION: char = '\u{fe0f}';
+
+/// Split `plate 🟩🟨⬛🟩⬛` (or `🟩🟨⬛🟩⬛ plate`) into word and colours
+fn split_emoji_row(input: &str) -> Option<(&str, &str)> {
+	let is_emoji_row = |part: &str| {
+		part.chars()
+			.all(|c| matches!(c, '🟩' | '🟧' | '🟨' | '🟦' | '⬛' | '⬜' | EMOJI_VARIATION))
+	};
+	let mut parts = input.split_whitespace();
+
+	match (parts.next(), parts.next(), parts.next()) {
+		(Some(word), Some(colours), None) if is_emoji_row(colours) => Some((word, colours)),
+		(Some(colours), Some(word), None) if is_emoji_row(colours) => Some((word, colours)),
+		_ => None,
+	}
+}
+
+fn tokenize_case_shorthand(input: &str) -> Result<Vec<GuessResultToken>, NotwordleError> {
+	let mut chars = input.chars();
+	let mut result: Vec<GuessResultToken> = vec![];
+
+	while let Some(c) = chars.next() {
+		let token = match c {
+			'?' | '!' => {
+				let letter = chars
+					.next()
+					.filter(char::is_ascii_alphabetic)
+					.ok_or_else(|| NotwordleError::InvalidShorthand(input.to_string()))?
+					.to_ascii_lowercase()
+					.to_string();
+
+				if c == '?' {
+					GuessResultToken::WrongPosition(letter)
+				} else {
+					GuessResultToken::Wrong(letter)
+				}
+			}
+			'A'..='Z' => GuessResultToken::Right(c.to_ascii_lowercase().to_string()),
+			'a'..='z' => GuessResultToken::Wrong(c.to_string()),
+			_ => return Err(NotwordleError::InvalidShorthand(input.to_string())),
+		};
+
+		result.push(token);
+	}
+
+	Ok(result)
+}
+
+/// A letter's present count in a guess row is its number of `Right` and
+/// `WrongPosition` entries. A `Wrong` copy of a letter in the same row means the
+/// answer has exactly that many, so it also caps the count.
+fn get_letter_bounds_from_results(guess_results: &[Vec<GuessResultToken>]) -> Vec<LetterBound> {
+	let mut bounds: Vec<LetterBound> = vec![];
 
 	for result in guess_results {
-		for (i, result_char) in result.iter().enumerate() {
-			match result_char {
-				GuessResultToken::Right(c) | GuessResultToken::WrongPosition(c) => {
-					include.push_str(c);
+		for row_bound in get_row_letter_bounds(result) {
+			if let Some(bound) = bounds.iter_mut().find(|b| b.letter == row_bound.letter) {
+				bound.min = bound.min.max(row_bound.min);
+				bound.max = match (bound.max, row_bound.max) {
+					(Some(a), Some(b)) => Some(a.min(b)),
+					(a, b) => a.or(b),
+				};
+			} else {
+				bounds.push(row_bound);
+			}
+		}
+	}
+
+	bounds
+}
+
+/// Letter bounds from a single guess row, see `get_letter_bounds_from_results`
+fn get_row_letter_bounds(result: &[GuessResultToken]) -> Vec<LetterBound> {
+	let mut row_bounds: Vec<LetterBound> = vec![];
+
+	for result_char in result {
+		let (GuessResultToken::Right(c)
+		| GuessResultToken::Wrong(c)
+		| GuessResultToken::WrongPosition(c)) = result_char;
+
+		for letter in c.chars() {
+			let index = row_bounds
+				.iter()
+				.position(|b| b.letter == letter)
+				.unwrap_or_else(|| {
+					row_bounds.push(LetterBound {
+						letter,
+						min: 0,
+						max: None,
+					});
+					row_bounds.len().saturating_sub(1)
+				});
+
+			if let Some(bound) = row_bounds.get_mut(index) {
+				match result_char {
+					GuessResultToken::Wrong(_) => bound.max = Some(0),
+					_ => bound.min = bound.min.saturating_add(1),
 				}
-				GuessResultToken::Wrong(c) => {
-					if !include.contains(c) {
-						exclude.push_str(c);
+			}
+		}
+	}
+
+	for bound in &mut row_bounds {
+		bound.max = bound.max.map(|_| bound.min);
+	}
+
+	row_bounds
+}
+
+/// The first reason no word could satisfy every guess result, if any
+fn find_contradiction(guess_results: &[Vec<GuessResultToken>]) -> Option<Contradiction> {
+	let row_bounds: Vec<Vec<LetterBound>> = guess_results
+		.iter()
+		.map(|result| get_row_letter_bounds(result))
+		.collect();
+
+	find_position_contradiction(guess_results)
+		.or_else(|| find_letter_count_contradiction(guess_results, &row_bounds))
+		.or_else(|| find_length_contradiction(guess_results, &row_bounds))
+}
+
+fn guess_tiles(
+	guess_results: &[Vec<GuessResultToken>],
+) -> impl Iterator<Item = (GuessTile, &GuessResultToken)> {
+	guess_results.iter().enumerate().flat_map(|(row, result)| {
+		result
+			.iter()
+			.enumerate()
+			.map(move |(position, token)| (GuessTile { row, position }, token))
+	})
+}
+
+/// The entries of `letter` in `row`, present ones or `Wrong` ones
+fn letter_tiles(
+	guess_results: &[Vec<GuessResultToken>],
+	row: usize,
+	letter: char,
+	present: bool,
+) -> impl Iterator<Item = GuessTile> {
+	guess_tiles(guess_results)
+		.filter(move |(tile, token)| {
+			tile.row == row
+				&& match token {
+					GuessResultToken::Wrong(c) => !present && c.contains(letter),
+					GuessResultToken::Right(c) | GuessResultToken::WrongPosition(c) => {
+						present && c.contains(letter)
 					}
 				}
+		})
+		.map(|(tile, _)| tile)
+}
+
+/// A position right in one row but with another letter right, or the same
+/// letter not right, in another
+fn find_position_contradiction(guess_results: &[Vec<GuessResultToken>]) -> Option<Contradiction> {
+	for (tile, token) in guess_tiles(guess_results) {
+		let GuessResultToken::Right(letter) = token else {
+			continue;
+		};
+
+		for (other, other_token) in guess_tiles(guess_results) {
+			if other.position != tile.position || other.row == tile.row {
+				continue;
+			}
+
+			match other_token {
+				GuessResultToken::Right(other_letter) if other_letter != letter => {
+					return Some(Contradiction::PositionTaken {
+						letter: letter.clone(),
+						tile,
+						other_letter: other_letter.clone(),
+						other,
+					});
+				}
+				GuessResultToken::Wrong(other_letter)
+				| GuessResultToken::WrongPosition(other_letter)
+					if other_letter == letter =>
+				{
+					return Some(Contradiction::RightAndMisplaced {
+						letter: letter.clone(),
+						tile,
+						other,
+					});
+				}
+				_ => {}
+			}
+		}
+	}
+
+	None
+}
+
+/// A row with more of a letter than another row caps it at
+fn find_letter_count_contradiction(
+	guess_results: &[Vec<GuessResultToken>],
+	row_bounds: &[Vec<LetterBound>],
+) -> Option<Contradiction> {
+	for (row, bounds) in row_bounds.iter().enumerate() {
+		for bound in bounds.iter().filter(|b| b.min > 0) {
+			for (other_row, other_bounds) in row_bounds.iter().enumerate() {
+				let max = other_bounds
+					.iter()
+					.find(|b| b.letter == bound.letter)
+					.and_then(|b| b.max);
+
+				if let Some(max) = max
+					&& max < bound.min
+					&& let Some(present) =
+						letter_tiles(guess_results, row, bound.letter, true).next()
+					&& let Some(absent) =
+						letter_tiles(guess_results, other_row, bound.letter, false).next()
+				{
+					return Some(Contradiction::LetterCount {
+						letter: bound.letter.to_string(),
+						min: bound.min,
+						present,
+						max,
+						absent,
+					});
+				}
 			}
+		}
+	}
+
+	None
+}
+
+/// More letters known to be in the word than it has positions
+fn find_length_contradiction(
+	guess_results: &[Vec<GuessResultToken>],
+	row_bounds: &[Vec<LetterBound>],
+) -> Option<Contradiction> {
+	let length = guess_results.first().map(Vec::len).unwrap_or_default();
+	let bounds = get_letter_bounds_from_results(guess_results);
+	let needed: usize = bounds.iter().map(|b| b.min).sum();
+
+	if needed <= length {
+		return None;
+	}
+
+	// the entries of each letter in the row that needs the most of it
+	let tiles = bounds
+		.iter()
+		.filter(|b| b.min > 0)
+		.filter_map(|b| {
+			let row = row_bounds.iter().position(|bounds| {
+				bounds
+					.iter()
+					.any(|row_bound| row_bound.letter == b.letter && row_bound.min == b.min)
+			})?;
+
+			Some(letter_tiles(guess_results, row, b.letter, true))
+		})
+		.flatten()
+		.collect();
+
+	Some(Contradiction::TooManyLetters {
+		needed,
+		length,
+		tiles,
+	})
+}
 
+fn get_match_args_from_results(
+	guess_results: &[Vec<GuessResultToken>],
+) -> (Vec<MatcherToken>, String, String) {
+	let bounds = get_letter_bounds_from_results(guess_results);
+	let include: String = bounds
+		.iter()
+		.filter(|b| b.min > 0)
+		.map(|b| b.letter)
+		.collect();
+	let exclude: String = bounds
+		.iter()
+		.filter(|b| b.min == 0 && b.max == Some(0))
+		.map(|b| b.letter)
+		.collect();
+	let mut match_tokens: Vec<MatcherToken> = vec![];
+
+	for result in guess_results {
+		for (i, result_char) in result.iter().enumerate() {
 			let resolved_op = match result_char {
 				GuessResultToken::Right(c) => MatcherToken::MatchAnyCharIn(c.clone()),
 				GuessResultToken::Wrong(c) | GuessResultToken::WrongPosition(c) => {
@@ -133,6 +859,10 @@ fn get_match_args_from_results(
 							acc.push_str(&a.clone());
 							MatcherToken::ExcludeAllCharsIn(unique_string(&acc))
 						}
+						// position already known from a previous guess
+						(_, Some(MatcherToken::MatchAnyCharIn(known))) => {
+							MatcherToken::MatchAnyCharIn(known.clone())
+						}
 						_ => candidate_op,
 					}
 				}
@@ -147,11 +877,7 @@ fn get_match_args_from_results(
 		}
 	}
 
-	(
-		match_tokens,
-		unique_string(&include),
-		unique_string(&exclude),
-	)
+	(match_tokens, include, exclude)
 }
 
 #[cfg(test)]
@@ -179,6 +905,90 @@ mod tokenize_tests {
 		);
 	}
 
+	#[test]
+	fn should_format_guess_result_as_entries() {
+		let tokens = tokenize_guess_result("plate:gy.g.").unwrap();
+
+		assert_eq!(format_guess_result(&tokens), "p ?l !a t !e");
+		assert_eq!(
+			tokenize_guess_result(&format_guess_result(&tokens)).unwrap(),
+			tokens
+		);
+		assert_eq!(
+			"?l".parse::<GuessResultToken>().unwrap(),
+			GuessResultToken::WrongPosition("l".to_string())
+		);
+		assert_eq!(
+			"p l".parse::<GuessResultToken>().unwrap_err(),
+			NotwordleError::InvalidGuessResultEntry("p l".to_string())
+		);
+	}
+
+	fn plate() -> Vec<GuessResultToken> {
+		vec![
+			GuessResultToken::Right("p".to_string()),
+			GuessResultToken::WrongPosition("l".to_string()),
+			GuessResultToken::Wrong("a".to_string()),
+			GuessResultToken::Right("t".to_string()),
+			GuessResultToken::Wrong("e".to_string()),
+		]
+	}
+
+	#[test]
+	fn should_parse_alternative_formats() {
+		assert_eq!(tokenize_guess_result("plate:gy.g.").unwrap(), plate());
+		assert_eq!(tokenize_guess_result(" PLATE : GYBGX ").unwrap(), plate());
+		assert_eq!(tokenize_guess_result("plate 🟩🟨⬛🟩⬛").unwrap(), plate());
+		assert_eq!(tokenize_guess_result("🟧🟦⬜🟧⬜ plate").unwrap(), plate());
+		assert_eq!(
+			tokenize_guess_result("plate:🟩🟨⬛\u{fe0f}🟩⬛\u{fe0f}").unwrap(),
+			plate()
+		);
+		assert_eq!(tokenize_guess_result("P?laTe").unwrap(), plate());
+		assert_eq!(tokenize_guess_result("P?L!AT!E").unwrap(), plate());
+		// one letter guesses keep their entry meaning
+		assert_eq!(
+			tokenize_guess_result("p").unwrap(),
+			vec![GuessResultToken::Right("p".to_string())]
+		);
+	}
+
+	#[test]
+	fn should_error_on_invalid_alternative_formats() {
+		assert_eq!(
+			tokenize_guess_result("plate:gy.g").unwrap_err(),
+			NotwordleError::MismatchedColourCode {
+				word: "plate".to_string(),
+				letters: 5,
+				colours: 4
+			}
+		);
+		assert_eq!(
+			tokenize_guess_result("plate:gy.gz").unwrap_err(),
+			NotwordleError::InvalidColourCode("gy.gz".to_string())
+		);
+		assert_eq!(
+			tokenize_guess_result("pl4te:gy.g.").unwrap_err(),
+			NotwordleError::InvalidGuessWord("pl4te".to_string())
+		);
+		assert_eq!(
+			tokenize_guess_result("plate 🟩🟨⬛🟩").unwrap_err(),
+			NotwordleError::MismatchedColourCode {
+				word: "plate".to_string(),
+				letters: 5,
+				colours: 4
+			}
+		);
+		assert_eq!(
+			tokenize_guess_result("Pla?").unwrap_err(),
+			NotwordleError::InvalidShorthand("Pla?".to_string())
+		);
+		assert_eq!(
+			tokenize_guess_result("Pl4te").unwrap_err(),
+			NotwordleError::InvalidShorthand("Pl4te".to_string())
+		);
+	}
+
 	#[test]
 	fn should_parse_guess_patterns() {
 		assert_eq!(
@@ -354,4 +1164,458 @@ mod match_args_tests {
 
 		assert_eq!(nw.refine(Some(&words)).unwrap(), vec!["datum"]);
 	}
+
+	#[test]
+	fn should_keep_known_positions() {
+		// word is place, guesses are plate then scale
+		let guesses = [
+			tokenize_guess_result("p l a !t e").unwrap(),
+			tokenize_guess_result("!s ?c a ?l e").unwrap(),
+		];
+		let (pattern, _, _) = get_match_args_from_results(&guesses);
+
+		assert_eq!(
+			pattern,
+			vec![
+				MatcherToken::MatchAnyCharIn("p".to_string()),
+				MatcherToken::MatchAnyCharIn("l".to_string()),
+				MatcherToken::MatchAnyCharIn("a".to_string()),
+				MatcherToken::ExcludeAllCharsIn("tl".to_string()),
+				MatcherToken::MatchAnyCharIn("e".to_string()),
+			]
+		);
+	}
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod edit_tests {
+	use super::*;
+
+	static WORDS: [&str; 4] = ["plate", "pilot", "place", "plume"];
+
+	fn registered(nw: &Notwordle) -> Vec<String> {
+		nw.guesses()
+			.map(|tokens| {
+				tokens
+					.iter()
+					.map(ToString::to_string)
+					.collect::<Vec<_>>()
+					.join(" ")
+			})
+			.collect()
+	}
+
+	#[test]
+	fn should_remove_guesses() {
+		let mut nw = Notwordle::default();
+
+		nw.register_guess_result("p l a !t e").unwrap();
+		nw.register_guess_result("p !i ?l !o !t").unwrap();
+
+		assert_eq!(nw.refine(Some(&WORDS)).unwrap(), vec!["place"]);
+		assert_eq!(
+			nw.remove_guess(0).unwrap(),
+			tokenize_guess_result("p l a !t e").unwrap()
+		);
+		assert_eq!(registered(&nw), vec!["p !i ?l !o !t"]);
+		assert_eq!(nw.refine(Some(&WORDS)).unwrap(), vec!["place", "plume"]);
+		assert_eq!(
+			nw.remove_guess(1).unwrap_err(),
+			NotwordleError::GuessIndexOutOfRange { index: 1, len: 1 }
+		);
+
+		nw.clear();
+
+		assert_eq!(nw.guesses().len(), 0);
+	}
+
+	#[test]
+	fn should_replace_guesses() {
+		let mut nw = Notwordle::default();
+
+		nw.register_guess_result("p l a !c e").unwrap();
+		nw.register_guess_result("p !i ?l !o !t").unwrap();
+		nw.replace_guess(0, "p l a !t e").unwrap();
+
+		assert_eq!(registered(&nw), vec!["p l a !t e", "p !i ?l !o !t"]);
+		assert_eq!(nw.refine(Some(&WORDS)).unwrap(), vec!["place"]);
+		assert_eq!(
+			nw.replace_guess(1, "p l a").unwrap_err(),
+			NotwordleError::InvalidGuessResultLength {
+				current: 3,
+				previous: 5
+			}
+		);
+		assert_eq!(
+			nw.replace_guess(2, "p l a t e").unwrap_err(),
+			NotwordleError::GuessIndexOutOfRange { index: 2, len: 2 }
+		);
+
+		// a lone guess result can change length
+		nw.remove_guess(1).unwrap();
+		nw.replace_guess(0, "c !a t").unwrap();
+
+		assert_eq!(registered(&nw), vec!["c !a t"]);
+	}
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod contradiction_tests {
+	use super::*;
+
+	fn tile(row: usize, position: usize) -> GuessTile {
+		GuessTile { row, position }
+	}
+
+	fn contradiction(results: &[&str]) -> Option<Contradiction> {
+		let mut nw = Notwordle::default();
+		let (last, previous) = results.split_last().unwrap();
+
+		for result in previous {
+			nw.register_guess_result(result).unwrap();
+		}
+
+		let err = nw.register_guess_result(last).unwrap_err();
+
+		assert_eq!(nw.guesses().len(), previous.len());
+
+		match err {
+			NotwordleError::Contradiction(contradiction) => Some(contradiction),
+			_ => None,
+		}
+	}
+
+	#[test]
+	fn should_find_contradictory_positions() {
+		assert_eq!(
+			contradiction(&["p l a !t e", "!s c a !r e"]),
+			Some(Contradiction::PositionTaken {
+				letter: "l".to_string(),
+				tile: tile(0, 1),
+				other_letter: "c".to_string(),
+				other: tile(1, 1),
+			})
+		);
+		assert_eq!(
+			contradiction(&["p l a !t e", "!s ?l a !c e"]),
+			Some(Contradiction::RightAndMisplaced {
+				letter: "l".to_string(),
+				tile: tile(0, 1),
+				other: tile(1, 1),
+			})
+		);
+	}
+
+	#[test]
+	fn should_find_contradictory_letter_counts() {
+		let found = contradiction(&["!p l a !t e", "!x !x !l !x !x"]).unwrap();
+
+		assert_eq!(
+			found,
+			Contradiction::LetterCount {
+				letter: "l".to_string(),
+				min: 1,
+				present: tile(0, 1),
+				max: 0,
+				absent: tile(1, 2),
+			}
+		);
+		assert_eq!(
+			found.to_string(),
+			"row 1 position 2 means at least 1 l in the word but row 2 position 3 means at most 0"
+		);
+		// the first guess has exactly two e, the second exactly one
+		assert_eq!(
+			contradiction(&["e ?e !r !i !e", "e !j !e !c !t"]),
+			Some(Contradiction::LetterCount {
+				letter: "e".to_string(),
+				min: 2,
+				present: tile(0, 0),
+				max: 1,
+				absent: tile(1, 2),
+			})
+		);
+		assert_eq!(
+			contradiction(&["?a ?b !c", "!d ?e ?f"]),
+			Some(Contradiction::TooManyLetters {
+				needed: 4,
+				length: 3,
+				tiles: vec![tile(0, 0), tile(0, 1), tile(1, 1), tile(1, 2)],
+			})
+		);
+	}
+
+	#[test]
+	fn should_check_replaced_guesses() {
+		let mut nw = Notwordle::default();
+
+		nw.register_guess_result("p l a !t e").unwrap();
+		nw.register_guess_result("p l !u !m e").unwrap();
+
+		assert!(matches!(
+			nw.replace_guess(1, "p !l !u !m e").unwrap_err(),
+			NotwordleError::Contradiction(Contradiction::RightAndMisplaced { .. })
+		));
+		nw.remove_guess(1).unwrap();
+		// the guess being replaced does not count
+		nw.replace_guess(0, "p !l !u !m e").unwrap();
+	}
+
+	#[test]
+	fn should_allow_consistent_guesses() {
+		let mut nw = Notwordle::default();
+
+		// word is eerie
+		nw.register_guess_result("!g e ?e !s e").unwrap();
+		nw.register_guess_result("!t !h ?e ?r e").unwrap();
+		nw.register_guess_result("e e r i e").unwrap();
+	}
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod explain_tests {
+	use super::*;
+
+	#[test]
+	fn should_explain_eliminated_words() {
+		// word is llama, guess is lolly
+		let mut nw = Notwordle::default();
+		let not_there = |position: usize, letters: &str| FailedConstraint::Position {
+			position,
+			token: MatcherToken::ExcludeAllCharsIn("l".to_string()),
+			letters: letters.to_string(),
+		};
+
+		nw.register_guess_result("l !o ?l !l !y").unwrap();
+
+		assert_eq!(nw.explain("llama").unwrap(), vec![]);
+		assert_eq!(
+			nw.explain("lulls").unwrap(),
+			vec![
+				not_there(2, "l"),
+				not_there(3, "l"),
+				FailedConstraint::LetterCount {
+					letter: 'l',
+					count: 3,
+					min: 2,
+					max: Some(2)
+				},
+			]
+		);
+		assert_eq!(
+			nw.explain("lamas").unwrap(),
+			vec![FailedConstraint::LetterCount {
+				letter: 'l',
+				count: 1,
+				min: 2,
+				max: Some(2)
+			}]
+		);
+		assert_eq!(
+			nw.explain("loyal").unwrap(),
+			vec![
+				FailedConstraint::Excluded('o'),
+				FailedConstraint::Excluded('y'),
+				FailedConstraint::Position {
+					position: 1,
+					token: MatcherToken::ExcludeAllCharsIn("o".to_string()),
+					letters: "o".to_string(),
+				},
+			]
+		);
+	}
+
+	#[test]
+	fn should_need_a_guess_result_to_explain() {
+		assert_eq!(
+			Notwordle::default().explain("crane").unwrap_err(),
+			NotwordleError::NoGuessResults
+		);
+	}
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod letter_bounds_tests {
+	use super::*;
+
+	fn bound(letter: char, min: usize, max: Option<usize>) -> LetterBound {
+		LetterBound { letter, min, max }
+	}
+
+	#[test]
+	fn should_derive_letter_bounds_from_guesses() {
+		// word is llama, guess is lolly
+		let guesses = [tokenize_guess_result("l !o ?l !l !y").unwrap()];
+
+		assert_eq!(
+			get_letter_bounds_from_results(&guesses),
+			vec![
+				bound('l', 2, Some(2)),
+				bound('o', 0, Some(0)),
+				bound('y', 0, Some(0)),
+			]
+		);
+
+		// word is sassy, guesses are asses then salsa
+		let guesses = [
+			tokenize_guess_result("?a ?s s !e ?s").unwrap(),
+			tokenize_guess_result("s a !l s !a").unwrap(),
+		];
+
+		assert_eq!(
+			get_letter_bounds_from_results(&guesses),
+			vec![
+				bound('a', 1, Some(1)),
+				bound('s', 3, None),
+				bound('e', 0, Some(0)),
+				bound('l', 0, Some(0)),
+			]
+		);
+	}
+
+	#[test]
+	fn should_require_repeated_letters() {
+		// word is llama, guess is hello
+		let mut nw = Notwordle::default();
+		let words = ["llama", "lamas", "local"];
+
+		nw.register_guess_result("!h !e ?l ?l !o").unwrap();
+
+		assert_eq!(nw.refine(Some(&words)).unwrap(), vec!["llama"]);
+
+		// word is sassy, guess is asses
+		let mut nw = Notwordle::default();
+		let words = ["basso", "sassy"];
+
+		nw.register_guess_result("?a ?s s !e ?s").unwrap();
+
+		assert_eq!(nw.refine(Some(&words)).unwrap(), vec!["sassy"]);
+	}
+
+	#[test]
+	fn should_cap_letters_with_a_wrong_copy() {
+		// word is elder, guess is eerie
+		let mut nw = Notwordle::default();
+		let words = ["elder", "eyeer", "ember"];
+
+		nw.register_guess_result("e ?e ?r !i !e").unwrap();
+
+		assert_eq!(nw.refine(Some(&words)).unwrap(), vec!["elder", "ember"]);
+	}
+
+	#[test]
+	fn should_need_every_copy_of_a_letter_in_the_word() {
+		// word is eerie, guess is geese: each e is right or misplaced, so the
+		// word has at least three
+		let mut nw = Notwordle::default();
+		let words = ["eerie", "terse", "deere"];
+
+		nw.register_guess_result("!g e ?e !s e").unwrap();
+
+		assert_eq!(nw.refine(Some(&words)).unwrap(), vec!["eerie"]);
+	}
+
+	#[test]
+	fn should_not_exclude_letter_when_wrong_copy_comes_first() {
+		// word is horse, guess is eerie
+		let mut nw = Notwordle::default();
+		let words = ["horse", "nurse", "terse"];
+
+		nw.register_guess_result("!e !e r !i e").unwrap();
+
+		assert_eq!(nw.refine(Some(&words)).unwrap(), vec!["horse", "nurse"]);
+	}
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod suggest_tests {
+	use super::*;
+
+	static WORDS: [&str; 9] = [
+		"batch", "hatch", "latch", "match", "patch", "watch", "blimp", "crane", "tacos",
+	];
+
+	#[test]
+	fn should_need_a_guess_result() {
+		assert_eq!(
+			Notwordle::default()
+				.suggest(Some(&WORDS), None, GuessPool::Remaining, 3)
+				.unwrap_err(),
+			NotwordleError::NoGuessResults
+		);
+	}
+
+	#[test]
+	fn should_suggest_from_pool() {
+		let mut nw = Notwordle::default();
+
+		// guess crane against an answer ending in "atch"
+		nw.register_guess_result("?c !r ?a !n !e").unwrap();
+
+		let remaining = nw
+			.suggest(Some(&WORDS), None, GuessPool::Remaining, 2)
+			.unwrap();
+
+		assert!(remaining.iter().all(|s| s.is_candidate));
+		assert_eq!(remaining.len(), 2);
+
+		let any = nw
+			.suggest(Some(&WORDS), None, GuessPool::Dictionary, 1)
+			.unwrap();
+
+		assert_eq!(any.first().map(|s| s.word), Some("blimp"));
+	}
+}
+
+#[cfg(all(test, feature = "serde"))]
+#[allow(clippy::unwrap_used)]
+mod serde_tests {
+	use super::*;
+
+	#[test]
+	fn should_serialize_guesses_as_rows() {
+		let mut nw = Notwordle::default();
+
+		nw.register_guess_result("plate:gy.g.").unwrap();
+		nw.register_guess_result("p !o l t s").unwrap();
+
+		let json = serde_json::to_string(&nw).unwrap();
+
+		assert_eq!(json, r#"{"guesses":["p ?l !a t !e","p !o l t s"]}"#);
+
+		let restored: Notwordle = serde_json::from_str(&json).unwrap();
+
+		assert!(restored.guesses().eq(nw.guesses()));
+	}
+
+	#[test]
+	fn should_serialize_tokens_as_entries() {
+		let tokens = tokenize_guess_result("p ?l !a").unwrap();
+		let json = serde_json::to_string(&tokens).unwrap();
+
+		assert_eq!(json, r#"["p","?l","!a"]"#);
+		assert_eq!(
+			serde_json::from_str::<Vec<GuessResultToken>>(&json).unwrap(),
+			tokens
+		);
+	}
+
+	#[test]
+	fn should_reject_invalid_saved_guesses() {
+		let err =
+			serde_json::from_str::<Notwordle>(r#"{"guesses":["p ?l !a t !e","p l"]}"#).unwrap_err();
+
+		assert!(err.to_string().starts_with("guess 2: "));
+
+		let err =
+			serde_json::from_str::<Notwordle>(r#"{"guesses":["p ?l !a t !e","!p ?l !a t !e"]}"#)
+				.unwrap_err();
+
+		assert!(err.to_string().starts_with("guess 2: "));
+	}
 }
@@ -0,0 +1,81 @@
+//! Order preserving loops over slices, spread across threads with the
+//! `parallel` feature and run on the current thread without it.
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Items for which `predicate` is true, in their original order
+#[cfg(feature = "parallel")]
+pub fn filter<T, F>(items: &[T], predicate: F) -> Vec<T>
+where
+	T: Copy + Send + Sync,
+	F: Fn(&T) -> bool + Send + Sync,
+{
+	items
+		.par_iter()
+		.filter(|item| predicate(item))
+		.copied()
+		.collect()
+}
+
+/// Items for which `predicate` is true, in their original order
+#[cfg(not(feature = "parallel"))]
+pub fn filter<T, F>(items: &[T], predicate: F) -> Vec<T>
+where
+	T: Copy + Send + Sync,
+	F: Fn(&T) -> bool + Send + Sync,
+{
+	items
+		.iter()
+		.filter(|item| predicate(item))
+		.copied()
+		.collect()
+}
+
+/// `f` applied to each item, in their original order
+#[cfg(feature = "parallel")]
+pub fn map<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+	T: Sync,
+	R: Send,
+	F: Fn(&T) -> R + Send + Sync,
+{
+	items.par_iter().map(f).collect()
+}
+
+/// `f` applied to each item, in their original order
+#[cfg(not(feature = "parallel"))]
+pub fn map<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+	T: Sync,
+	R: Send,
+	F: Fn(&T) -> R + Send + Sync,
+{
+	items.iter().map(f).collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn should_keep_order() {
+		let items: Vec<usize> = (0..10_000).collect();
+
+		assert_eq!(
+			filter(&items, |n| n % 3 == 0),
+			items
+				.iter()
+				.copied()
+				.filter(|n| n % 3 == 0)
+				.collect::<Vec<_>>()
+		);
+		assert_eq!(
+			map(&items, |n| n.saturating_mul(2)),
+			items
+				.iter()
+				.map(|n| n.saturating_mul(2))
+				.collect::<Vec<_>>()
+		);
+	}
+}
@@ -0,0 +1,276 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use crate::data::WordFrequencies;
+use crate::feedback::feedback_code;
+use crate::parallel;
+
+/// Which words to consider as the next guess
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GuessPool {
+	/// only words that could still be the answer
+	#[default]
+	Remaining,
+	/// any dictionary word of the right length, even if already ruled out
+	Dictionary,
+}
+
+/// A candidate guess, scored by how well it splits the remaining words
+#[derive(Clone, Debug, PartialEq)]
+pub struct Suggestion {
+	pub word: &'static str,
+	/// information (in bits) expected from the feedback to this guess
+	pub entropy: f64,
+	/// number of words expected to remain after this guess
+	pub expected_remaining: f64,
+	/// whether this guess could be the answer
+	pub is_candidate: bool,
+}
+
+/// Score each of `guesses` against the words in `remaining` and return the
+/// best `limit`, most informative first.
+///
+/// Words are compared byte by byte, so are expected to be lowercase ascii.
+#[must_use]
+pub fn rank_guesses(
+	guesses: &[&'static str],
+	remaining: &[&'static str],
+	limit: usize,
+) -> Vec<Suggestion> {
+	rank_guesses_weighted(guesses, remaining, None, limit)
+}
+
+/// Like `rank_guesses`, treating common words in `frequencies` as more likely
+/// answers than rare ones, so guesses that split the likely answers well rank
+/// first.
+#[must_use]
+pub fn rank_guesses_weighted(
+	guesses: &[&'static str],
+	remaining: &[&'static str],
+	frequencies: Option<&WordFrequencies>,
+	limit: usize,
+) -> Vec<Suggestion> {
+	if remaining.is_empty() {
+		return vec![];
+	}
+
+	let weights = answer_weights(remaining, frequencies);
+	let mut suggestions = parallel::map(guesses, |guess| {
+		score_guess_against(guess, remaining, weights.as_deref())
+	});
+
+	suggestions.sort_by(compare_suggestions);
+	suggestions.truncate(limit);
+	suggestions
+}
+
+/// Like `rank_guesses`, for a guess played on several boards at once. A guess
+/// scores the information it is expected to give summed over every board, so
+/// words that split many boards well rank first.
+#[must_use]
+pub fn rank_guesses_across_boards(
+	guesses: &[&'static str],
+	boards: &[Vec<&'static str>],
+	limit: usize,
+) -> Vec<Suggestion> {
+	rank_guesses_across_boards_weighted(guesses, boards, None, limit)
+}
+
+/// Like `rank_guesses_across_boards`, weighting answers like
+/// `rank_guesses_weighted`.
+#[must_use]
+pub fn rank_guesses_across_boards_weighted(
+	guesses: &[&'static str],
+	boards: &[Vec<&'static str>],
+	frequencies: Option<&WordFrequencies>,
+	limit: usize,
+) -> Vec<Suggestion> {
+	let boards: Vec<(&Vec<&'static str>, Option<Vec<f64>>)> = boards
+		.iter()
+		.filter(|b| !b.is_empty())
+		.map(|b| (b, answer_weights(b, frequencies)))
+		.collect();
+
+	if boards.is_empty() {
+		return vec![];
+	}
+
+	let mut suggestions = parallel::map(guesses, |guess| {
+		boards.iter().fold(
+			Suggestion {
+				word: guess,
+				entropy: 0.0,
+				expected_remaining: 0.0,
+				is_candidate: false,
+			},
+			|total, (remaining, weights)| {
+				let board = score_guess_against(guess, remaining, weights.as_deref());
+
+				Suggestion {
+					entropy: total.entropy + board.entropy,
+					expected_remaining: total.expected_remaining + board.expected_remaining,
+					is_candidate: total.is_candidate || board.is_candidate,
+					..total
+				}
+			},
+		)
+	});
+
+	suggestions.sort_by(compare_suggestions);
+	suggestions.truncate(limit);
+	suggestions
+}
+
+/// How likely each remaining word is to be the answer, relative to the others.
+/// Grows with the log of a word's frequency, so common words count for more
+/// without drowning out everything else. `None` when there are no frequencies,
+/// as every word is as likely.
+fn answer_weights(remaining: &[&str], frequencies: Option<&WordFrequencies>) -> Option<Vec<f64>> {
+	let frequencies = frequencies?;
+
+	Some(
+		remaining
+			.iter()
+			.map(|word| {
+				let count = frequencies.get(word).unwrap_or_default();
+
+				f64::from(count.saturating_add(1).ilog2()) + 1.0
+			})
+			.collect(),
+	)
+}
+
+fn score_guess_against(
+	guess: &'static str,
+	remaining: &[&'static str],
+	weights: Option<&[f64]>,
+) -> Suggestion {
+	let (entropy, expected_remaining) = weighted_guess_entropy(guess, remaining, weights);
+
+	Suggestion {
+		word: guess,
+		entropy,
+		expected_remaining,
+		is_candidate: remaining.contains(&guess),
+	}
+}
+
+/// Information (in bits) expected from the feedback to `guess`, and the number
+/// of words expected to remain after it
+#[must_use]
+pub fn guess_entropy(guess: &str, remaining: &[&str]) -> (f64, f64) {
+	weighted_guess_entropy(guess, remaining, None)
+}
+
+/// Like `guess_entropy`, where each remaining word is as likely to be the
+/// answer as its weight. Without weights every word weighs 1.
+fn weighted_guess_entropy(guess: &str, remaining: &[&str], weights: Option<&[f64]>) -> (f64, f64) {
+	let mut buckets: HashMap<u64, (f64, u32)> = HashMap::new();
+	let mut total = 0.0;
+
+	for (index, answer) in remaining.iter().enumerate() {
+		let weight = weights
+			.and_then(|weights| weights.get(index))
+			.copied()
+			.unwrap_or(1.0);
+		let (bucket_weight, bucket_count) = buckets
+			.entry(feedback_code(guess.as_bytes(), answer.as_bytes()))
+			.or_default();
+
+		*bucket_weight += weight;
+		total += weight;
+		*bucket_count = bucket_count.saturating_add(1);
+	}
+
+	// summed in a fixed order so equally good guesses always tie exactly
+	let mut buckets: Vec<(f64, u32)> = buckets.into_values().collect();
+
+	buckets.sort_unstable_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+
+	buckets
+		.iter()
+		.fold((0.0, 0.0), |(entropy, expected), (weight, count)| {
+			let p = weight / total;
+
+			(
+				p.mul_add(-p.log2(), entropy),
+				p.mul_add(f64::from(*count), expected),
+			)
+		})
+}
+
+fn compare_suggestions(a: &Suggestion, b: &Suggestion) -> Ordering {
+	b.entropy
+		.total_cmp(&a.entropy)
+		.then_with(|| a.expected_remaining.total_cmp(&b.expected_remaining))
+		.then_with(|| b.is_candidate.cmp(&a.is_candidate))
+		.then_with(|| a.word.cmp(b.word))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn should_rank_guesses_by_information() {
+		let remaining = ["batch", "hatch", "latch", "match", "patch", "watch"];
+		let guesses = ["match", "blimp", "xyzzy"];
+		let ranked = rank_guesses(&guesses, &remaining, 2);
+
+		assert_eq!(
+			ranked.iter().map(|s| s.word).collect::<Vec<_>>(),
+			vec!["blimp", "match"]
+		);
+
+		let best = ranked.first().unwrap();
+
+		assert!(!best.is_candidate);
+		assert!((best.expected_remaining - 8.0 / 6.0).abs() < 1e-9);
+		assert!(ranked.get(1).unwrap().is_candidate);
+
+		let useless = rank_guesses(&["xyzzy"], &remaining, 1);
+
+		assert!(useless.first().unwrap().entropy.abs() < 1e-9);
+		assert!((useless.first().unwrap().expected_remaining - 6.0).abs() < 1e-9);
+	}
+
+	#[test]
+	fn should_weight_common_answers() {
+		let remaining = ["crane", "crate", "craze", "crave"];
+		// tells crane and crate apart, or craze and crave
+		let guesses = ["xntqq", "qvzxx"];
+		let frequencies: WordFrequencies = [("crane", 1000), ("crate", 1000)].into_iter().collect();
+		let ranked = |frequencies| {
+			rank_guesses_weighted(&guesses, &remaining, frequencies, 1)
+				.first()
+				.unwrap()
+				.word
+		};
+
+		assert_eq!(ranked(None), "qvzxx");
+		assert_eq!(ranked(Some(&frequencies)), "xntqq");
+	}
+
+	#[test]
+	fn should_rank_guesses_across_boards() {
+		let boards = vec![
+			vec!["batch", "hatch", "latch", "match"],
+			vec!["blimp", "climb"],
+			vec![],
+		];
+		let ranked = rank_guesses_across_boards(&["blimp", "match", "xyzzy"], &boards, 3);
+
+		assert_eq!(
+			ranked.iter().map(|s| s.word).collect::<Vec<_>>(),
+			vec!["blimp", "match", "xyzzy"]
+		);
+
+		let best = ranked.first().unwrap();
+
+		// 2 bits splitting the first board, 1 bit the second
+		assert!((best.entropy - 3.0).abs() < 1e-9);
+		assert!(best.is_candidate);
+		assert!(rank_guesses_across_boards(&["blimp"], &[vec![]], 1).is_empty());
+	}
+}
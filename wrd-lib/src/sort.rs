@@ -0,0 +1,171 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::data::Dictionary;
+use crate::parallel;
+use crate::solver::guess_entropy;
+
+/// Orders for matched or remaining words
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SortKey {
+	/// as the words appear in the dictionary
+	#[default]
+	Dictionary,
+	Alphabetical,
+	Shortest,
+	Longest,
+	/// highest scrabble score first
+	Scrabble,
+	/// most common first, from the dictionary's frequency table. Words
+	/// without a frequency come last
+	Frequency,
+	/// most informative guess first, scored against the other words of the
+	/// same length like the solver does
+	Score,
+}
+
+impl SortKey {
+	/// The next sort key, wrapping around, for cycling through them
+	#[must_use]
+	pub const fn next(self) -> Self {
+		match self {
+			Self::Dictionary => Self::Alphabetical,
+			Self::Alphabetical => Self::Shortest,
+			Self::Shortest => Self::Longest,
+			Self::Longest => Self::Scrabble,
+			Self::Scrabble => Self::Frequency,
+			Self::Frequency => Self::Score,
+			Self::Score => Self::Dictionary,
+		}
+	}
+}
+
+impl fmt::Display for SortKey {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let label = match self {
+			Self::Dictionary => "dictionary",
+			Self::Alphabetical => "a-z",
+			Self::Shortest => "shortest",
+			Self::Longest => "longest",
+			Self::Scrabble => "scrabble",
+			Self::Frequency => "frequency",
+			Self::Score => "score",
+		};
+
+		write!(f, "{label}")
+	}
+}
+
+/// Sort words in place. Words that tie keep their order. `dictionary` is where
+/// `SortKey::Frequency` looks up frequencies.
+///
+/// `SortKey::Score` compares every word with every other word of its length,
+/// so takes a while for thousands of words.
+pub fn sort_words(words: &mut [&str], key: SortKey, dictionary: &Dictionary) {
+	match key {
+		SortKey::Dictionary => {}
+		SortKey::Alphabetical => words.sort_unstable(),
+		SortKey::Shortest => words.sort_by_key(|word| word.chars().count()),
+		SortKey::Longest => words.sort_by_key(|word| Reverse(word.chars().count())),
+		SortKey::Scrabble => words.sort_by_key(|word| Reverse(scrabble_score(word))),
+		SortKey::Frequency => {
+			words.sort_by_cached_key(|word| Reverse(dictionary.frequency(word)));
+		}
+		SortKey::Score => {
+			let mut by_length: HashMap<usize, Vec<&str>> = HashMap::new();
+
+			for word in words.iter() {
+				by_length.entry(word.len()).or_default().push(word);
+			}
+
+			let entropies: HashMap<&str, f64> = parallel::map(words, |word| {
+				let same_length = by_length.get(&word.len()).map_or(&[][..], Vec::as_slice);
+
+				(*word, guess_entropy(word, same_length).0)
+			})
+			.into_iter()
+			.collect();
+			let entropy = |word: &str| entropies.get(word).copied().unwrap_or_default();
+
+			words.sort_by(|a, b| entropy(b).total_cmp(&entropy(a)));
+		}
+	}
+}
+
+/// Sum of the scrabble tile values of each letter a-z in the word
+#[must_use]
+pub fn scrabble_score(word: &str) -> u32 {
+	word.chars()
+		.map(|c| match c {
+			'a' | 'e' | 'i' | 'l' | 'n' | 'o' | 'r' | 's' | 't' | 'u' => 1,
+			'd' | 'g' => 2,
+			'b' | 'c' | 'm' | 'p' => 3,
+			'f' | 'h' | 'v' | 'w' | 'y' => 4,
+			'k' => 5,
+			'j' | 'x' => 8,
+			'q' | 'z' => 10,
+			_ => 0,
+		})
+		.sum()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn sorted(key: SortKey) -> Vec<&'static str> {
+		let mut words = vec!["fuzzy", "cat", "aardvark", "boat", "crane", "slate"];
+
+		sort_words(&mut words, key, &Dictionary::Moby);
+		words
+	}
+
+	#[test]
+	fn should_sort_words() {
+		assert_eq!(
+			sorted(SortKey::Dictionary),
+			vec!["fuzzy", "cat", "aardvark", "boat", "crane", "slate"]
+		);
+		assert_eq!(
+			sorted(SortKey::Alphabetical),
+			vec!["aardvark", "boat", "cat", "crane", "fuzzy", "slate"]
+		);
+		assert_eq!(
+			sorted(SortKey::Shortest),
+			vec!["cat", "boat", "fuzzy", "crane", "slate", "aardvark"]
+		);
+		assert_eq!(
+			sorted(SortKey::Longest),
+			vec!["aardvark", "fuzzy", "crane", "slate", "boat", "cat"]
+		);
+		assert_eq!(
+			sorted(SortKey::Scrabble),
+			vec!["fuzzy", "aardvark", "crane", "boat", "cat", "slate"]
+		);
+		// fuzzy can't tell crane from slate, they tell all three apart
+		assert_eq!(
+			sorted(SortKey::Score).get(..3),
+			Some(&["crane", "slate", "fuzzy"][..])
+		);
+	}
+
+	#[test]
+	fn should_score_scrabble_tiles() {
+		assert_eq!(scrabble_score("quiz"), 22);
+		assert_eq!(scrabble_score("crane"), 7);
+		assert_eq!(scrabble_score(""), 0);
+	}
+
+	#[test]
+	fn should_cycle_sort_keys() {
+		let mut key = SortKey::default();
+
+		for _ in 0..7 {
+			key = key.next();
+		}
+
+		assert_eq!(key, SortKey::Dictionary);
+		assert_eq!(SortKey::Alphabetical.to_string(), "a-z");
+	}
+}
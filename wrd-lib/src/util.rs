@@ -8,6 +8,18 @@ pub fn unique_string(str: &str) -> String {
 	})
 }
 
+/// How many times each char appears, in order of first appearance
+pub fn char_counts(letters: &str) -> Vec<(char, usize)> {
+	letters.chars().fold(vec![], |mut counts, c| {
+		match counts.iter_mut().find(|(letter, _)| *letter == c) {
+			Some((_, n)) => *n = n.saturating_add(1),
+			None => counts.push((c, 1)),
+		}
+
+		counts
+	})
+}
+
 pub fn non_empty_str(str: &str) -> Option<&str> {
 	let trimmed = str.trim();
 
@@ -26,4 +38,10 @@ mod tests {
 	fn should_return_a_unique_string_keeping_order() {
 		assert_eq!(unique_string("accbcdeacbede"), "acbde".to_string());
 	}
+
+	#[test]
+	fn should_count_chars_in_order_of_first_appearance() {
+		assert_eq!(char_counts("eerie"), vec![('e', 3), ('r', 1), ('i', 1)]);
+		assert_eq!(char_counts(""), vec![]);
+	}
 }
@@ -9,12 +9,16 @@ use ratatui::symbols::border;
 use ratatui::widgets::{Block, Padding, Paragraph, StatefulWidgetRef, Widget, WidgetRef};
 use tui_input::Input;
 use tui_input::backend::crossterm::EventHandler;
-use wrd_lib::get_dictionary;
+use wrd_lib::{Matcher, SortKey, WithinMode, get_dictionary, sort_words};
 
-use super::{AppTab, AppTabIo, Tab};
+use super::{AppTab, AppTabIo, Tab, capped_sort, next_sort};
+use crate::session::MatchInputs;
 use crate::state::AppState;
 use crate::widgets::WordGrid;
 
+/// Matches to look for at a time, so broad patterns like `**` show quickly
+const PAGE_SIZE: usize = 500;
+
 #[derive(Default, Debug, Clone, PartialEq)]
 enum TargetInput {
 	#[default]
@@ -44,35 +48,33 @@ pub struct MatchWords<'a> {
 	within_input: Input,
 	include_input: Input,
 	exclude_input: Input,
+	matcher: Option<Matcher>,
 	results: Vec<String>,
+	has_more: bool,
+	sort: SortKey,
+	within_mode: WithinMode,
 	word_grid: WordGrid<'a>,
 }
 
 impl Default for MatchWords<'_> {
 	fn default() -> Self {
-		let mut word_grid = WordGrid::default();
-		let pattern = "* * * **";
-		let within = "trubador";
-		let include = "d";
-		let exclude = "";
-		let results: Vec<_> = wrd_lib::match_words(pattern, include, exclude, within, None)
-			.unwrap_or_default()
-			.into_iter()
-			.map(str::to_string)
-			.collect();
-
-		word_grid.update(&results);
-
-		Self {
+		let mut match_words = Self {
 			target_input: TargetInput::default(),
 			is_active: false,
-			pattern_input: Input::new(pattern.into()),
-			within_input: Input::new(within.into()),
-			include_input: Input::new(include.into()),
-			exclude_input: Input::new(exclude.into()),
-			results,
-			word_grid,
-		}
+			pattern_input: Input::new("* * * **".into()),
+			within_input: Input::new("trubador".into()),
+			include_input: Input::new("d".into()),
+			exclude_input: Input::new(String::new()),
+			matcher: None,
+			results: vec![],
+			has_more: false,
+			sort: SortKey::default(),
+			within_mode: WithinMode::Rack,
+			word_grid: WordGrid::default(),
+		};
+
+		match_words.refresh_results(&AppState::default());
+		match_words
 	}
 }
 
@@ -87,20 +89,82 @@ impl MatchWords<'_> {
 		};
 	}
 
+	/// Put back the inputs saved by the last run
+	pub fn restore(&mut self, inputs: &MatchInputs, state: &AppState) {
+		self.pattern_input = Input::new(inputs.pattern.clone());
+		self.within_input = Input::new(inputs.within.clone());
+		self.include_input = Input::new(inputs.include.clone());
+		self.exclude_input = Input::new(inputs.exclude.clone());
+		self.refresh_results(state);
+	}
+
+	pub fn inputs(&self) -> MatchInputs {
+		MatchInputs {
+			pattern: self.pattern_input.value().to_string(),
+			within: self.within_input.value().to_string(),
+			include: self.include_input.value().to_string(),
+			exclude: self.exclude_input.value().to_string(),
+		}
+	}
+
 	fn refresh_results(&mut self, state: &AppState) {
-		let Ok(dict) = get_dictionary(&state.dictionary) else {
-			return;
-		};
-		let results = wrd_lib::match_words(
+		self.matcher = Matcher::new(
 			self.pattern_input.value(),
 			self.include_input.value(),
 			self.exclude_input.value(),
 			self.within_input.value(),
-			Some(dict),
+			self.within_mode,
 		)
-		.unwrap_or_default();
+		.ok();
+		self.results.clear();
+		self.load_more_results(state);
+	}
+
+	/// Switch between using `within` letters like rack tiles and reusing them
+	/// any number of times, as `wrd mw --repeat-within` does
+	fn toggle_within_mode(&mut self, state: &AppState) {
+		self.within_mode = match self.within_mode {
+			WithinMode::Rack => WithinMode::Repeat,
+			WithinMode::Repeat => WithinMode::Rack,
+		};
+		self.refresh_results(state);
+	}
 
-		self.results = results.into_iter().map(str::to_string).collect();
+	fn cycle_sort(&mut self, state: &AppState) {
+		self.sort = next_sort(self.sort, state);
+		self.results.clear();
+		self.load_more_results(state);
+	}
+
+	/// Look for the next page of matches. Sorted matches can't be paged, so are
+	/// all found at once.
+	fn load_more_results(&mut self, state: &AppState) {
+		let Ok(dict) = get_dictionary(&state.dictionary) else {
+			return;
+		};
+		let Some(matcher) = &self.matcher else {
+			self.has_more = false;
+			self.word_grid.update(&self.results);
+			return;
+		};
+
+		if self.sort != SortKey::Dictionary {
+			let mut found = matcher.find_all(dict);
+
+			self.sort = capped_sort(self.sort, found.len());
+			sort_words(&mut found, self.sort, &state.dictionary);
+			self.results = found.into_iter().map(str::to_string).collect();
+			self.has_more = false;
+			self.word_grid.update(&self.results);
+			return;
+		}
+
+		let limit = self.results.len().saturating_add(PAGE_SIZE);
+		let mut unseen = matcher.matches(dict).skip(self.results.len());
+
+		self.results
+			.extend(unseen.by_ref().take(PAGE_SIZE).map(str::to_string));
+		self.has_more = self.results.len() == limit && unseen.next().is_some();
 		self.word_grid.update(&self.results);
 	}
 
@@ -169,9 +233,26 @@ impl MatchWords<'_> {
 	}
 
 	fn render_results(&self, area: Rect, buf: &mut Buffer) {
+		let within = match self.within_mode {
+			WithinMode::Rack => "rack",
+			WithinMode::Repeat => "repeat",
+		};
+		let title = if self.has_more {
+			format!(
+				" Results (first {}, <m>ore, </> sort: {}, <r> within: {within}) ",
+				self.results.len(),
+				self.sort
+			)
+		} else {
+			format!(
+				" Results ({}, </> sort: {}, <r> within: {within}) ",
+				self.results.len(),
+				self.sort
+			)
+		};
 		let block = Block::bordered()
 			.border_set(border::PLAIN)
-			.title(" Results ")
+			.title(title)
 			.padding(Padding::horizontal(1));
 		let grid_area = block.inner(area);
 
@@ -207,6 +288,9 @@ impl AppTabIo for MatchWords<'_> {
 				KeyCode::Char('w') if not_focused => self.target_input = TargetInput::Within,
 				KeyCode::Char('i') if not_focused => self.target_input = TargetInput::Include,
 				KeyCode::Char('e') if not_focused => self.target_input = TargetInput::Exclude,
+				KeyCode::Char('m') if not_focused && self.has_more => self.load_more_results(state),
+				KeyCode::Char('/') if not_focused => self.cycle_sort(state),
+				KeyCode::Char('r') if not_focused => self.toggle_within_mode(state),
 				KeyCode::Tab => self.target_input = self.target_input.next(),
 				KeyCode::Esc => self.target_input = TargetInput::None,
 				KeyCode::Enter => self.refresh_results(state),
@@ -1,13 +1,18 @@
 use color_eyre::eyre::Result;
 use ratatui::crossterm::event::Event;
 use ratatui::widgets::StatefulWidgetRef;
+use wrd_lib::{DictionaryRegistry, SortKey};
 
 mod match_words;
+mod multi_board;
 mod not_wordle;
+mod play;
 mod settings;
 
 pub use match_words::MatchWords;
+pub use multi_board::MultiBoard;
 pub use not_wordle::NotWordle;
+pub use play::Play;
 pub use settings::Settings;
 
 use crate::state::AppState;
@@ -17,6 +22,8 @@ pub enum Tab {
 	#[default]
 	MatchWords,
 	NotWordle,
+	MultiBoard,
+	Play,
 	Settings,
 }
 
@@ -35,3 +42,33 @@ impl std::fmt::Debug for dyn AppTabIo {
 		write!(f, "{}", self.label())
 	}
 }
+
+/// The sort order after `sort`, skipping frequency order when the dictionary
+/// has no word frequencies to sort by
+fn next_sort(sort: SortKey, state: &AppState) -> SortKey {
+	let next = sort.next();
+
+	if next == SortKey::Frequency
+		&& DictionaryRegistry::global()
+			.frequencies(&state.dictionary)
+			.is_none()
+	{
+		next.next()
+	} else {
+		next
+	}
+}
+
+/// Most words to sort by `SortKey::Score`, which compares every word with every
+/// other word so freezes the app for seconds past a few thousand
+const MAX_SCORED_WORDS: usize = 2_000;
+
+/// `sort`, or dictionary order when `sort` is score order and there are too
+/// many words to score quickly
+fn capped_sort(sort: SortKey, word_count: usize) -> SortKey {
+	if sort == SortKey::Score && word_count > MAX_SCORED_WORDS {
+		SortKey::Dictionary
+	} else {
+		sort
+	}
+}
@@ -0,0 +1,320 @@
+use color_eyre::eyre::Result;
+use ratatui::buffer::Buffer;
+use ratatui::crossterm::event::{Event, KeyCode};
+use ratatui::layout::Constraint::{Fill, Length};
+use ratatui::layout::{Layout, Rect};
+use ratatui::style::Style;
+use ratatui::style::palette::tailwind;
+use ratatui::symbols::border;
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Padding, Paragraph, StatefulWidgetRef, Widget, WidgetRef};
+use tui_input::Input;
+use tui_input::backend::crossterm::EventHandler;
+use wrd_lib::{
+	DictionaryRegistry, GuessPool, GuessResultToken, MultiNotwordle, Suggestion, get_dictionary,
+};
+
+use super::not_wordle::{format_tokenized, render_suggestions};
+use super::{AppTab, AppTabIo, Tab};
+use crate::state::AppState;
+use crate::widgets::WordGrid;
+
+const BOARD_COUNTS: [usize; 3] = [2, 4, 8];
+const SUGGESTION_COUNT: usize = 20;
+
+#[derive(Debug, Default)]
+struct Board<'a> {
+	results: Vec<Option<Vec<GuessResultToken>>>,
+	remaining: usize,
+	solved: bool,
+	word_grid: WordGrid<'a>,
+}
+
+#[derive(Debug)]
+pub struct MultiBoard<'a> {
+	is_active: bool,
+	is_editing: bool,
+	board_count: usize,
+	input: Input,
+	notwordle: MultiNotwordle,
+	boards: Vec<Board<'a>>,
+	suggestions: Vec<Suggestion>,
+	suggest_pool: GuessPool,
+	error: Option<String>,
+}
+
+impl Default for MultiBoard<'_> {
+	fn default() -> Self {
+		let mut boards = Self {
+			is_active: false,
+			is_editing: false,
+			board_count: 4,
+			input: Input::new(String::new()),
+			notwordle: MultiNotwordle::default(),
+			boards: vec![],
+			suggestions: vec![],
+			suggest_pool: GuessPool::default(),
+			error: None,
+		};
+
+		boards.reset();
+		boards
+	}
+}
+
+impl MultiBoard<'_> {
+	fn reset(&mut self) {
+		self.notwordle = MultiNotwordle::new(self.board_count);
+		self.suggestions.clear();
+		self.error = None;
+		self.input.reset();
+		self.boards = (0..self.board_count)
+			.map(|_| Board {
+				word_grid: WordGrid::with_columns(2),
+				..Board::default()
+			})
+			.collect();
+	}
+
+	fn cycle_board_count(&mut self) {
+		let next = BOARD_COUNTS
+			.iter()
+			.position(|count| *count == self.board_count)
+			.map_or(0, |index| index.saturating_add(1));
+
+		self.board_count = BOARD_COUNTS
+			.get(next)
+			.or_else(|| BOARD_COUNTS.first())
+			.copied()
+			.unwrap_or(2);
+		self.reset();
+	}
+
+	fn submit_row(&mut self, state: &AppState) {
+		let row = self.input.value().trim();
+
+		if row.is_empty() {
+			return;
+		}
+
+		match self.notwordle.register_guess_row(row) {
+			Ok(tokenized) => {
+				for (board, result) in self.boards.iter_mut().zip(tokenized) {
+					board.results.push(result);
+				}
+
+				self.input.reset();
+				self.error = None;
+				self.refresh_results(state);
+			}
+			Err(e) => self.error = Some(e.to_string()),
+		}
+	}
+
+	/// Remove the last row, e.g. to fix a mistyped result
+	fn undo_row(&mut self, state: &AppState) {
+		if self.notwordle.undo_guess().is_none() {
+			return;
+		}
+
+		for board in &mut self.boards {
+			board.results.pop();
+		}
+
+		self.error = None;
+		self.refresh_results(state);
+	}
+
+	fn refresh_results(&mut self, state: &AppState) {
+		let Ok(dict) = get_dictionary(&state.dictionary) else {
+			return;
+		};
+
+		if let Ok(refined) = self.notwordle.refine(Some(dict)) {
+			for ((board, remaining), solved) in self
+				.boards
+				.iter_mut()
+				.zip(refined)
+				.zip(self.notwordle.solved())
+			{
+				let words: Vec<String> = remaining.into_iter().map(str::to_string).collect();
+
+				board.remaining = words.len();
+				board.solved = solved;
+				board.word_grid.update(if solved { &[] } else { &words });
+			}
+		}
+
+		self.suggestions = self
+			.notwordle
+			.suggest(
+				Some(dict),
+				DictionaryRegistry::global()
+					.frequencies(&state.dictionary)
+					.as_deref(),
+				self.suggest_pool,
+				SUGGESTION_COUNT,
+			)
+			.unwrap_or_default();
+	}
+
+	fn toggle_suggest_pool(&mut self, state: &AppState) {
+		self.suggest_pool = match self.suggest_pool {
+			GuessPool::Remaining => GuessPool::Dictionary,
+			GuessPool::Dictionary => GuessPool::Remaining,
+		};
+		self.refresh_results(state);
+	}
+
+	fn render_input(&self, area: Rect, buf: &mut Buffer, state: &mut AppState) {
+		let [help_area, input_area, error_area] =
+			Layout::vertical([Length(1), Length(1), Length(1)]).areas(area);
+		let [label_area, value_area] = Layout::horizontal([Length(7), Fill(1)]).areas(input_area);
+
+		Paragraph::new(format!(
+			" <g>uess e.g. crane:g....|.y...  <-> undo  <b>oards: {}  <n>ew",
+			self.board_count
+		))
+		.style(Style::default().fg(tailwind::NEUTRAL.c500).bold())
+		.render(help_area, buf);
+
+		Paragraph::new(" row >")
+			.style(if self.is_editing {
+				Style::default().fg(tailwind::BLUE.c600).bold()
+			} else {
+				Style::default().dim()
+			})
+			.render(label_area, buf);
+
+		let width = usize::from(value_area.width);
+		let scroll = self.input.visual_scroll(width);
+
+		Paragraph::new(self.input.value())
+			.scroll((0, u16::try_from(scroll).unwrap_or_default()))
+			.render(value_area, buf);
+
+		if self.is_editing {
+			let x = u16::try_from(
+				self.input
+					.visual_cursor()
+					.max(scroll)
+					.saturating_sub(scroll),
+			)
+			.unwrap_or_default();
+
+			state.cursor_position = Some((value_area.x.saturating_add(x), value_area.y));
+		}
+
+		if let Some(error) = &self.error {
+			Paragraph::new(format!(" {error}"))
+				.style(Style::default().fg(tailwind::RED.c400))
+				.render(error_area, buf);
+		}
+	}
+
+	fn render_boards(&self, area: Rect, buf: &mut Buffer) {
+		let areas = Layout::horizontal(vec![Fill(1); self.boards.len()]).split(area);
+		let rows = u16::try_from(self.notwordle.guesses().len()).unwrap_or_default();
+
+		for (index, (board, board_area)) in self.boards.iter().zip(areas.iter()).enumerate() {
+			let title = if board.solved {
+				format!(" {} solved ", index.saturating_add(1))
+			} else if self.notwordle.guesses().is_empty() {
+				format!(" {} ", index.saturating_add(1))
+			} else {
+				format!(" {}: {} left ", index.saturating_add(1), board.remaining)
+			};
+			let block = Block::bordered()
+				.border_set(border::PLAIN)
+				.title(title)
+				.padding(Padding::horizontal(1));
+			let [results_area, grid_area] =
+				Layout::vertical([Length(rows.saturating_add(1)), Fill(1)])
+					.areas(block.inner(*board_area));
+			let lines: Vec<Line> = board
+				.results
+				.iter()
+				.map(|result| {
+					result
+						.as_ref()
+						.map_or_else(Line::default, |result| Line::from(format_tokenized(result)))
+				})
+				.collect();
+
+			Paragraph::new(lines).render(results_area, buf);
+			board.word_grid.render_ref(grid_area, buf);
+			block.render(*board_area, buf);
+		}
+	}
+}
+
+impl AppTabIo for MultiBoard<'_> {
+	fn label(&self) -> &'static str {
+		"Boards"
+	}
+
+	fn tab(&self) -> Tab {
+		Tab::MultiBoard
+	}
+
+	fn set_active(&mut self, is_active: bool, state: &mut AppState) {
+		self.is_active = is_active;
+		self.is_editing = false;
+
+		if is_active {
+			self.refresh_results(state);
+		}
+	}
+
+	fn handle_event(&mut self, event: &Event, state: &mut AppState) -> Result<()> {
+		if !self.is_active {
+			return Ok(());
+		}
+
+		if let Event::Key(key_event) = event {
+			if self.is_editing {
+				match key_event.code {
+					KeyCode::Esc => {
+						self.is_editing = false;
+						state.cursor_position = None;
+					}
+					KeyCode::Enter => self.submit_row(state),
+					_ => {
+						self.input.handle_event(event);
+					}
+				}
+
+				return Ok(());
+			}
+
+			match key_event.code {
+				KeyCode::Char('g') => self.is_editing = true,
+				KeyCode::Char('b') => self.cycle_board_count(),
+				KeyCode::Char('n') => self.reset(),
+				KeyCode::Char('-') => self.undo_row(state),
+				KeyCode::Char('*') => self.toggle_suggest_pool(state),
+				_ => (),
+			}
+		}
+
+		Ok(())
+	}
+}
+
+impl StatefulWidgetRef for MultiBoard<'_> {
+	type State = AppState;
+
+	fn render_ref(&self, area: Rect, buf: &mut Buffer, state: &mut AppState) {
+		let [input_area, results_area] = Layout::vertical([Length(3), Fill(1)]).areas(area);
+		let [boards_area, suggestions_area] =
+			Layout::horizontal([Fill(1), Length(34)]).areas(results_area);
+
+		state.cursor_position = None;
+
+		self.render_input(input_area, buf, state);
+		self.render_boards(boards_area, buf);
+		render_suggestions(&self.suggestions, self.suggest_pool, suggestions_area, buf);
+	}
+}
+
+impl AppTab for MultiBoard<'_> {}
@@ -1,5 +1,5 @@
 use color_eyre::eyre::Result;
-use crossterm::event::KeyCode;
+use crossterm::event::{KeyCode, KeyModifiers};
 use ratatui::buffer::Buffer;
 use ratatui::crossterm::event::Event;
 use ratatui::layout::Constraint::{Fill, Length, Min};
@@ -11,16 +11,23 @@ use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Padding, Paragraph, StatefulWidgetRef, Widget, WidgetRef};
 use tui_input::Input;
 use tui_input::backend::crossterm::EventHandler;
-use wrd_lib::{GuessResultToken, get_dictionary};
+use wrd_lib::{
+	DictionaryRegistry, GuessPool, GuessResultToken, Notwordle, NotwordleError, SortKey,
+	Suggestion, format_guess_result, get_dictionary, sort_words, tokenize_guess_result,
+};
 
-use super::{AppTab, AppTabIo, Tab};
+use super::{AppTab, AppTabIo, Tab, capped_sort, next_sort};
 use crate::state::AppState;
 use crate::widgets::WordGrid;
 
 #[derive(Debug)]
 struct GuessResult {
 	input: Input,
+	/// what is registered for this row, `None` until it is committed
 	tokenized: Option<Vec<GuessResultToken>>,
+	error: Option<String>,
+	/// positions that contradict another row
+	conflicts: Vec<usize>,
 }
 
 impl Default for GuessResult {
@@ -28,45 +35,178 @@ impl Default for GuessResult {
 		Self {
 			input: Input::new(String::new()),
 			tokenized: None,
+			error: None,
+			conflicts: vec![],
 		}
 	}
 }
 
+const SUGGESTION_COUNT: usize = 20;
+
 #[derive(Default, Debug)]
 pub struct NotWordle<'a> {
+	// only the last row can be uncommitted, so rows line up with the guesses
+	// in `registered`
 	guesses: Vec<GuessResult>,
+	registered: Notwordle,
 	word_grid: WordGrid<'a>,
 	edit_guess: Option<u16>,
 	results: Vec<String>,
+	suggestions: Vec<Suggestion>,
+	suggest_pool: GuessPool,
+	sort: SortKey,
+	/// the word being looked up, while it is typed
+	lookup: Option<Input>,
+	/// the word looked up and why it is or is not left
+	explanation: Option<(String, Vec<String>)>,
 	is_active: bool,
 }
 
 impl NotWordle<'_> {
 	fn add_guess(&mut self) {
-		self.guesses.push(GuessResult::default());
+		// finish the uncommitted row before starting another
+		if self
+			.guesses
+			.last()
+			.is_none_or(|guess| guess.tokenized.is_some())
+		{
+			self.guesses.push(GuessResult::default());
+		}
+
 		self.edit_guess =
 			Some(u16::try_from(self.guesses.len().saturating_sub(1)).unwrap_or_default());
 	}
 
-	fn refresh_results(&mut self, state: &AppState) {
-		let mut not_wordle = wrd_lib::Notwordle::default();
+	/// Put back the guesses saved by the last run
+	pub fn restore(&mut self, notwordle: &Notwordle, state: &AppState) {
+		self.guesses = notwordle
+			.guesses()
+			.map(|tokens| GuessResult {
+				input: Input::new(format_guess_result(tokens)),
+				tokenized: Some(tokens.to_vec()),
+				..GuessResult::default()
+			})
+			.collect();
+		self.registered = notwordle.clone();
+
+		// an empty row to go on from, as after committing a guess
+		if !self.guesses.is_empty() {
+			self.guesses.push(GuessResult::default());
+		}
+
+		self.refresh_results(state);
+	}
+
+	pub const fn registered(&self) -> &Notwordle {
+		&self.registered
+	}
+
+	fn remove_guess(&mut self, index: usize, state: &AppState) {
+		if index >= self.guesses.len() {
+			return;
+		}
+
+		self.mark_conflicts(None);
+
+		if self.guesses.remove(index).tokenized.is_some() {
+			// rows line up with registered guesses, so the index is in range
+			let _ = self.registered.remove_guess(index);
+		}
+
+		if self.edit_guess.is_some() {
+			self.edit_guess = self
+				.guesses
+				.len()
+				.checked_sub(1)
+				.and_then(|last| u16::try_from(index.min(last)).ok());
+		}
+
+		self.refresh_results(state);
+	}
+
+	fn undo_guess(&mut self, state: &AppState) {
+		if let Some(last) = self.guesses.len().checked_sub(1) {
+			self.remove_guess(last, state);
+		}
+	}
 
+	fn refresh_results(&mut self, state: &AppState) {
 		// @TODO: surface error
 		let Ok(dict) = get_dictionary(&state.dictionary) else {
 			return;
 		};
 
-		for guess in &mut *self.guesses {
-			guess.tokenized = not_wordle
-				.register_guess_result(guess.input.value().trim())
-				.ok();
-		}
-
 		// @TODO: surface error
-		if let Ok(results) = not_wordle.refine(Some(dict)) {
+		if let Ok(mut results) = self.registered.refine(Some(dict)) {
+			self.sort = capped_sort(self.sort, results.len());
+			sort_words(&mut results, self.sort, &state.dictionary);
 			self.results = results.into_iter().map(str::to_string).collect();
 			self.word_grid.update(&self.results);
 		}
+
+		self.suggestions = self
+			.registered
+			.suggest(
+				Some(dict),
+				DictionaryRegistry::global()
+					.frequencies(&state.dictionary)
+					.as_deref(),
+				self.suggest_pool,
+				SUGGESTION_COUNT,
+			)
+			.unwrap_or_default();
+
+		if let Some((word, _)) = self.explanation.take() {
+			self.explain(word, state);
+		}
+	}
+
+	fn start_lookup(&mut self, state: &mut AppState) {
+		self.stop_editing(state);
+		self.lookup = Some(Input::default());
+	}
+
+	fn commit_lookup(&mut self, state: &mut AppState) {
+		state.cursor_position = None;
+
+		if let Some(lookup) = self.lookup.take() {
+			let word = lookup.value().trim().to_lowercase();
+
+			if word.is_empty() {
+				self.explanation = None;
+			} else {
+				self.explain(word, state);
+			}
+		}
+	}
+
+	fn explain(&mut self, word: String, state: &AppState) {
+		let mut lines = vec![];
+
+		if get_dictionary(&state.dictionary).is_ok_and(|dict| !dict.contains(&word.as_str())) {
+			lines.push(format!("{word} is not in the dictionary"));
+		}
+
+		match self.registered.explain(&word) {
+			Ok(failed) if failed.is_empty() => lines.push("passes every guess result".to_string()),
+			Ok(failed) => lines.extend(failed.iter().map(|constraint| format!("- {constraint}"))),
+			Err(err) => lines.push(err.to_string()),
+		}
+
+		self.explanation = Some((word, lines));
+	}
+
+	fn toggle_suggest_pool(&mut self, state: &AppState) {
+		self.suggest_pool = match self.suggest_pool {
+			GuessPool::Remaining => GuessPool::Dictionary,
+			GuessPool::Dictionary => GuessPool::Remaining,
+		};
+		self.refresh_results(state);
+	}
+
+	fn cycle_sort(&mut self, state: &AppState) {
+		self.sort = next_sort(self.sort, state);
+		self.refresh_results(state);
 	}
 
 	fn forward_event_to_input(&mut self, event: &Event) {
@@ -84,7 +224,9 @@ impl NotWordle<'_> {
 		let (intro, inputs) = areas.split_at_checked(1).unwrap_or_default();
 
 		if let Some(intro_area) = intro.first() {
-			Paragraph::new(" <+> add guess result")
+			Paragraph::new(
+				" <+> add guess result, e.g. p ?l !a t !e | plate:gy.g. | P?laTe  <-> undo  <ctrl+d> remove row  <?> why",
+			)
 				.style(Style::default().fg(tailwind::NEUTRAL.c500).bold())
 				.render(*intro_area, buf);
 		}
@@ -112,14 +254,25 @@ impl NotWordle<'_> {
 					))
 					.render(input_area, buf);
 
-				if let Some(tokenized) = &guess.tokenized {
-					let formatted = format_tokenized(tokenized);
-
-					Paragraph::new(Line::from(formatted)).render(formatted_area, buf);
+				// a row that failed to commit shows what was entered
+				let shown = if guess.error.is_some() {
+					tokenize_guess_result(guess.input.value().trim()).ok()
 				} else {
-					Paragraph::new("").render(formatted_area, buf);
+					guess.tokenized.clone()
+				};
+				let mut formatted = shown
+					.as_deref()
+					.map(|tokenized| {
+						highlight_conflicts(format_tokenized(tokenized), &guess.conflicts)
+					})
+					.unwrap_or_default();
+
+				if let Some(error) = &guess.error {
+					formatted.push(Span::from(format!(" {error}")).fg(tailwind::RED.c500));
 				}
 
+				Paragraph::new(Line::from(formatted)).render(formatted_area, buf);
+
 				if is_active {
 					let scroll = guess.input.visual_scroll(input_width);
 					let x =
@@ -132,14 +285,62 @@ impl NotWordle<'_> {
 	}
 
 	fn commit_guess(&mut self, state: &AppState) {
+		let Some(index) = self.edit_guess.map(usize::from) else {
+			return;
+		};
+		let Some(guess) = self.guesses.get(index) else {
+			return;
+		};
+		let value = guess.input.value().trim();
+
+		if value.is_empty() {
+			return;
+		}
+
+		let result = if guess.tokenized.is_some() {
+			self.registered.replace_guess(index, value)
+		} else {
+			self.registered.register_guess_result(value)
+		};
+
+		self.mark_conflicts(result.as_ref().err());
+
+		let Some(guess) = self.guesses.get_mut(index) else {
+			return;
+		};
+
+		match result {
+			Ok(tokenized) => {
+				guess.tokenized = Some(tokenized);
+				guess.error = None;
+			}
+			// stay on the row to fix it, the previous result is still used
+			Err(err) => {
+				guess.error = Some(err.to_string());
+				return;
+			}
+		}
+
 		self.refresh_results(state);
 
-		if let Some(index) = self.edit_guess {
-			let len = u16::try_from(self.guesses.len()).unwrap_or_default();
-			if index == len.saturating_sub(1) {
-				self.add_guess();
-			} else {
-				self.go_to_next_guess();
+		if index == self.guesses.len().saturating_sub(1) {
+			self.add_guess();
+		} else {
+			self.go_to_next_guess();
+		}
+	}
+
+	/// Highlight the entries a contradiction points at, clearing any before
+	fn mark_conflicts(&mut self, err: Option<&NotwordleError>) {
+		for guess in &mut self.guesses {
+			guess.conflicts.clear();
+		}
+
+		if let Some(NotwordleError::Contradiction(contradiction)) = err {
+			for tile in contradiction.tiles() {
+				if let Some(guess) = self.guesses.get_mut(tile.row) {
+					guess.conflicts.push(tile.position);
+				}
 			}
 		}
 	}
@@ -162,10 +363,14 @@ impl NotWordle<'_> {
 	}
 
 	fn render_results(&self, area: Rect, buf: &mut Buffer) {
-		let title = if self.guesses.is_empty() {
+		let title = if self.registered.guesses().len() == 0 {
 			" Enter a guess result ".to_string()
 		} else {
-			format!(" {} words remaining ", self.results.len())
+			format!(
+				" {} words remaining, </> sort: {} ",
+				self.results.len(),
+				self.sort
+			)
 		};
 
 		let block = Block::bordered()
@@ -178,13 +383,80 @@ impl NotWordle<'_> {
 		self.word_grid.render_ref(grid_area, buf);
 	}
 
+	fn render_lookup(&self, area: Rect, buf: &mut Buffer, state: &mut AppState) {
+		let block = Block::bordered()
+			.border_set(border::PLAIN)
+			.title(" Why is a word left out? <?> ")
+			.padding(Padding::horizontal(1));
+		let [word_area, lines_area] =
+			Layout::vertical([Length(1), Fill(1)]).areas(block.inner(area));
+
+		block.render(area, buf);
+
+		if let Some(lookup) = &self.lookup {
+			let scroll = lookup.visual_scroll(usize::from(word_area.width));
+			let x =
+				u16::try_from(lookup.visual_cursor().saturating_sub(scroll)).unwrap_or_default();
+
+			Paragraph::new(lookup.value())
+				.scroll((0, u16::try_from(scroll).unwrap_or_default()))
+				.render(word_area, buf);
+			state.cursor_position = Some((word_area.x.saturating_add(x), word_area.y));
+		} else if let Some((word, _)) = &self.explanation {
+			Paragraph::new(word.as_str())
+				.style(Style::default().fg(tailwind::ORANGE.c400).bold())
+				.render(word_area, buf);
+		}
+
+		if self.lookup.is_none()
+			&& let Some((_, lines)) = &self.explanation
+		{
+			let lines: Vec<Line> = lines.iter().map(|line| Line::from(line.as_str())).collect();
+
+			Paragraph::new(lines).render(lines_area, buf);
+		}
+	}
+
 	const fn stop_editing(&mut self, state: &mut AppState) {
 		self.edit_guess = None;
 		state.cursor_position = None;
 	}
 }
 
-fn format_tokenized(tokenized: &[GuessResultToken]) -> Vec<Span<'_>> {
+pub(super) fn render_suggestions(
+	suggestions: &[Suggestion],
+	pool: GuessPool,
+	area: Rect,
+	buf: &mut Buffer,
+) {
+	let title = match pool {
+		GuessPool::Remaining => " Suggestions <*> remaining ",
+		GuessPool::Dictionary => " Suggestions <*> all words ",
+	};
+	let block = Block::bordered()
+		.border_set(border::PLAIN)
+		.title(title)
+		.padding(Padding::horizontal(1));
+	let lines: Vec<Line> = suggestions
+		.iter()
+		.map(|suggestion| {
+			let word = Span::from(format!("{:<12}", suggestion.word));
+
+			Line::from(vec![
+				if suggestion.is_candidate {
+					word.fg(tailwind::ORANGE.c400).bold()
+				} else {
+					word.bold()
+				},
+				Span::from(format!("{:>5.2} bits", suggestion.entropy)).dim(),
+			])
+		})
+		.collect();
+
+	Paragraph::new(lines).block(block).render(area, buf);
+}
+
+pub(super) fn format_tokenized(tokenized: &[GuessResultToken]) -> Vec<Span<'_>> {
 	tokenized
 		.iter()
 		.cloned()
@@ -202,6 +474,20 @@ fn format_tokenized(tokenized: &[GuessResultToken]) -> Vec<Span<'_>> {
 		.collect()
 }
 
+fn highlight_conflicts<'a>(spans: Vec<Span<'a>>, conflicts: &[usize]) -> Vec<Span<'a>> {
+	spans
+		.into_iter()
+		.enumerate()
+		.map(|(position, span)| {
+			if conflicts.contains(&position) {
+				span.bg(tailwind::RED.c500).fg(tailwind::WHITE).bold()
+			} else {
+				span
+			}
+		})
+		.collect()
+}
+
 fn upper_span<'a>(c: &str) -> Span<'a> {
 	Span::from(format!(" {} ", c.to_uppercase()))
 }
@@ -246,10 +532,37 @@ impl AppTabIo for NotWordle<'_> {
 		}
 
 		if let Event::Key(key_event) = event {
+			if let Some(lookup) = &mut self.lookup {
+				match key_event.code {
+					KeyCode::Esc => {
+						self.lookup = None;
+						self.explanation = None;
+						state.cursor_position = None;
+					}
+					KeyCode::Enter => self.commit_lookup(state),
+					_ => {
+						lookup.handle_event(event);
+					}
+				}
+
+				return Ok(());
+			}
+
 			let is_editing = self.edit_guess.is_some();
 
 			match key_event.code {
 				KeyCode::Char('+') if !is_editing => self.add_guess(),
+				KeyCode::Char('*') if !is_editing => self.toggle_suggest_pool(state),
+				KeyCode::Char('/') if !is_editing => self.cycle_sort(state),
+				KeyCode::Char('-') if !is_editing => self.undo_guess(state),
+				KeyCode::Char('?') if !is_editing => self.start_lookup(state),
+				KeyCode::Char('d')
+					if is_editing && key_event.modifiers.contains(KeyModifiers::CONTROL) =>
+				{
+					if let Some(index) = self.edit_guess {
+						self.remove_guess(usize::from(index), state);
+					}
+				}
 				KeyCode::Esc => self.stop_editing(state),
 				KeyCode::Enter if is_editing => self.commit_guess(state),
 				KeyCode::Tab => self.go_to_next_guess(),
@@ -277,8 +590,25 @@ impl StatefulWidgetRef for NotWordle<'_> {
 		let [inputs_area, results_area] =
 			Layout::vertical([Length(len.saturating_add(1)), Fill(1)]).areas(area);
 
+		let [words_area, suggestions_area] =
+			Layout::horizontal([Fill(1), Length(34)]).areas(results_area);
+		let lookup_height = match (&self.lookup, &self.explanation) {
+			(Some(_), _) => 3,
+			(None, Some((_, lines))) => u16::try_from(lines.len())
+				.unwrap_or_default()
+				.saturating_add(3),
+			(None, None) => 0,
+		};
+		let [words_area, lookup_area] =
+			Layout::vertical([Fill(1), Length(lookup_height)]).areas(words_area);
+
 		self.render_inputs(inputs_area, buf, state);
-		self.render_results(results_area, buf);
+		self.render_results(words_area, buf);
+
+		if lookup_height > 0 {
+			self.render_lookup(lookup_area, buf, state);
+		}
+		render_suggestions(&self.suggestions, self.suggest_pool, suggestions_area, buf);
 	}
 }
 
@@ -0,0 +1,325 @@
+use color_eyre::eyre::Result;
+use ratatui::buffer::Buffer;
+use ratatui::crossterm::event::{Event, KeyCode};
+use ratatui::layout::Constraint::{Fill, Length};
+use ratatui::layout::{Layout, Rect};
+use ratatui::style::palette::tailwind;
+use ratatui::style::{Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Padding, Paragraph, StatefulWidgetRef, Widget};
+use tui_input::Input;
+use tui_input::backend::crossterm::EventHandler;
+use wrd_lib::{Game, GameOptions, GameStatus, SecretWord, get_dictionary};
+
+use super::not_wordle::format_tokenized;
+use super::{AppTab, AppTabIo, Tab};
+use crate::state::AppState;
+
+#[derive(Debug)]
+pub struct Play {
+	is_active: bool,
+	is_editing: bool,
+	/// options for the next game, the running game keeps the ones it started
+	/// with
+	options: GameOptions,
+	input: Input,
+	/// the seed being typed, to replay or share a game
+	seed_input: Option<Input>,
+	game: Option<Game>,
+	/// how the running game picked its word
+	secret: Option<SecretWord>,
+	message: Option<String>,
+}
+
+const MAX_GUESS_LIMIT: usize = 12;
+
+impl Default for Play {
+	fn default() -> Self {
+		Self {
+			is_active: false,
+			is_editing: false,
+			options: GameOptions::default(),
+			input: Input::new(String::new()),
+			seed_input: None,
+			game: None,
+			secret: None,
+			message: None,
+		}
+	}
+}
+
+impl Play {
+	fn new_game(&mut self, secret: SecretWord, state: &AppState) {
+		let game = get_dictionary(&state.dictionary)
+			.map_err(|e| e.to_string())
+			.and_then(|dict| {
+				Game::new(dict, secret.clone(), self.options).map_err(|e| e.to_string())
+			});
+
+		self.input.reset();
+
+		match game {
+			Ok(game) => {
+				self.game = Some(game);
+				self.secret = Some(secret);
+				self.message = None;
+				self.is_editing = true;
+			}
+			Err(e) => {
+				self.game = None;
+				self.secret = None;
+				self.message = Some(e);
+			}
+		}
+	}
+
+	/// Change the guess limit of the next game by `delta`, keeping at least
+	/// one guess
+	fn change_guess_limit(&mut self, delta: isize) {
+		self.options.max_guesses = self
+			.options
+			.max_guesses
+			.saturating_add_signed(delta)
+			.clamp(1, MAX_GUESS_LIMIT);
+	}
+
+	fn commit_seed(&mut self, state: &mut AppState) {
+		let Some(input) = self.seed_input.take() else {
+			return;
+		};
+
+		state.cursor_position = None;
+
+		match input.value().trim().parse() {
+			Ok(seed) => self.new_game(SecretWord::Seed(seed), state),
+			Err(_) => {
+				self.message = Some(format!(
+					"invalid seed: {}, expected a whole number",
+					input.value().trim()
+				));
+			}
+		}
+	}
+
+	fn submit_guess(&mut self) {
+		let Some(game) = &mut self.game else {
+			return;
+		};
+
+		match game.guess(self.input.value()) {
+			Ok(_) => {
+				self.input.reset();
+				self.message = match game.status() {
+					GameStatus::Won => Some("solved!".to_string()),
+					GameStatus::Lost => Some(format!("the word was {}", game.answer())),
+					GameStatus::InProgress => None,
+				};
+
+				if game.status() != GameStatus::InProgress {
+					self.is_editing = false;
+				}
+			}
+			Err(e) => self.message = Some(e.to_string()),
+		}
+	}
+
+	fn render_help(&self, area: Rect, buf: &mut Buffer, state: &mut AppState) {
+		let [keys_area, next_area, game_area] =
+			Layout::vertical([Length(1), Length(1), Length(1)]).areas(area);
+		let style = Style::default().fg(tailwind::NEUTRAL.c500).bold();
+		let on_off = |on: bool| if on { "on" } else { "off" };
+
+		Paragraph::new(" <g>uess  <n>ew game  <d>aily word  <s>eed")
+			.style(style)
+			.render(keys_area, buf);
+		Paragraph::new(format!(
+			" next game: <h>ard mode: {}  <+/-> guesses: {}",
+			on_off(self.options.hard_mode),
+			self.options.max_guesses
+		))
+		.style(style)
+		.render(next_area, buf);
+
+		if let Some(input) = &self.seed_input {
+			let label = " seed: ";
+
+			Paragraph::new(format!("{label}{}", input.value()))
+				.style(Style::default().fg(tailwind::BLUE.c600).bold())
+				.render(game_area, buf);
+
+			let x = u16::try_from(label.len().saturating_add(input.visual_cursor()))
+				.unwrap_or_default();
+
+			state.cursor_position = Some((game_area.x.saturating_add(x), game_area.y));
+		} else if let Some(game) = &self.game {
+			let secret = match &self.secret {
+				Some(SecretWord::Seed(seed)) => format!("seed {seed}"),
+				Some(SecretWord::Daily(day)) => format!("daily word {day}"),
+				Some(SecretWord::Word(_)) | None => "chosen word".to_string(),
+			};
+
+			Paragraph::new(format!(
+				" this game: {secret}, hard mode: {}, guesses: {}",
+				on_off(game.options().hard_mode),
+				game.options().max_guesses
+			))
+			.style(Style::default().dim())
+			.render(game_area, buf);
+		}
+	}
+
+	fn render_board(&self, game: &Game, area: Rect, buf: &mut Buffer, state: &mut AppState) {
+		let options = game.options();
+		let rows = Layout::vertical(vec![Length(1); options.max_guesses]).split(area);
+
+		for (index, row_area) in rows.iter().enumerate() {
+			let line = if let Some((_, result)) = game.guesses().get(index) {
+				Line::from(format_tokenized(result))
+			} else if index == game.guesses().len() && game.status() == GameStatus::InProgress {
+				let typed = self.input.value().to_uppercase();
+				let mut spans: Vec<Span> = typed
+					.chars()
+					.take(options.word_length)
+					.map(|c| Span::from(format!(" {c} ")).bold())
+					.collect();
+
+				if self.is_editing {
+					let x = u16::try_from(spans.len().saturating_mul(3).saturating_add(1))
+						.unwrap_or_default();
+
+					state.cursor_position = Some((row_area.x.saturating_add(x), row_area.y));
+				}
+
+				spans.resize(options.word_length, Span::from(" _ ").dim());
+				Line::from(spans)
+			} else {
+				Line::from(vec![Span::from(" _ ").dim(); options.word_length])
+			};
+
+			Paragraph::new(line).render(*row_area, buf);
+		}
+	}
+
+	fn render_outcome(&self, area: Rect, buf: &mut Buffer) {
+		let [message_area, share_area] = Layout::vertical([Length(2), Fill(1)]).areas(area);
+
+		if let Some(message) = &self.message {
+			Paragraph::new(message.as_str())
+				.style(Style::default().fg(tailwind::BLUE.c400).bold())
+				.render(message_area, buf);
+		}
+
+		if let Some(game) = &self.game
+			&& game.status() != GameStatus::InProgress
+		{
+			Paragraph::new(game.share_grid()).render(share_area, buf);
+		}
+	}
+}
+
+impl AppTabIo for Play {
+	fn label(&self) -> &'static str {
+		"Play"
+	}
+
+	fn tab(&self) -> Tab {
+		Tab::Play
+	}
+
+	fn set_active(&mut self, is_active: bool, state: &mut AppState) {
+		self.is_active = is_active;
+		self.is_editing = false;
+		self.seed_input = None;
+
+		if is_active && self.game.is_none() {
+			self.new_game(SecretWord::random(), state);
+		}
+	}
+
+	fn handle_event(&mut self, event: &Event, state: &mut AppState) -> Result<()> {
+		if !self.is_active {
+			return Ok(());
+		}
+
+		if let Event::Key(key_event) = event {
+			if let Some(input) = &mut self.seed_input {
+				match key_event.code {
+					KeyCode::Esc => {
+						self.seed_input = None;
+						state.cursor_position = None;
+					}
+					KeyCode::Enter => self.commit_seed(state),
+					_ => {
+						input.handle_event(event);
+					}
+				}
+
+				return Ok(());
+			}
+
+			if self.is_editing {
+				match key_event.code {
+					KeyCode::Esc => {
+						self.is_editing = false;
+						state.cursor_position = None;
+					}
+					KeyCode::Enter => self.submit_guess(),
+					_ => {
+						self.input.handle_event(event);
+					}
+				}
+
+				return Ok(());
+			}
+
+			let in_progress = self
+				.game
+				.as_ref()
+				.is_some_and(|game| game.status() == GameStatus::InProgress);
+
+			match key_event.code {
+				KeyCode::Char('g') if in_progress => self.is_editing = true,
+				KeyCode::Char('n') => self.new_game(SecretWord::random(), state),
+				KeyCode::Char('d') => self.new_game(SecretWord::today(), state),
+				KeyCode::Char('s') => self.seed_input = Some(Input::new(String::new())),
+				KeyCode::Char('h') => self.options.hard_mode = !self.options.hard_mode,
+				KeyCode::Char('+') => self.change_guess_limit(1),
+				KeyCode::Char('-') => self.change_guess_limit(-1),
+				_ => (),
+			}
+		}
+
+		Ok(())
+	}
+}
+
+impl StatefulWidgetRef for Play {
+	type State = AppState;
+
+	fn render_ref(&self, area: Rect, buf: &mut Buffer, state: &mut AppState) {
+		let block = Block::new().padding(Padding::horizontal(1));
+		let rows = self
+			.game
+			.as_ref()
+			.map_or(0, |game| game.options().max_guesses);
+		let [help_area, board_area, outcome_area] = Layout::vertical([
+			Length(4),
+			Length(u16::try_from(rows.saturating_add(1)).unwrap_or_default()),
+			Fill(1),
+		])
+		.areas(block.inner(area));
+
+		state.cursor_position = None;
+
+		self.render_help(help_area, buf, state);
+
+		if let Some(game) = &self.game {
+			self.render_board(game, board_area, buf, state);
+		}
+
+		self.render_outcome(outcome_area, buf);
+		block.render(area, buf);
+	}
+}
+
+impl AppTab for Play {}
@@ -1,12 +1,14 @@
 use color_eyre::eyre::Result;
 use ratatui::buffer::Buffer;
 use ratatui::crossterm::event::{Event, KeyCode};
-use ratatui::layout::Constraint::Length;
+use ratatui::layout::Constraint::{Length, Min};
 use ratatui::layout::{Layout, Rect};
 use ratatui::style::Style;
 use ratatui::style::palette::tailwind;
 use ratatui::widgets::{Block, Padding, Paragraph, StatefulWidgetRef, Widget};
-use wrd_lib::Dictionary;
+use tui_input::Input;
+use tui_input::backend::crossterm::EventHandler;
+use wrd_lib::{DataError, Dictionary, DictionaryRegistry};
 
 use super::{AppTab, AppTabIo, Tab};
 use crate::state::AppState;
@@ -16,6 +18,9 @@ pub struct Settings {
 	is_active: bool,
 	highlighted_dict_index: Option<usize>,
 	dict_options: Vec<Dictionary>,
+	is_editing_path: bool,
+	path_input: Input,
+	path_error: Option<String>,
 }
 
 impl Default for Settings {
@@ -23,7 +28,10 @@ impl Default for Settings {
 		Self {
 			is_active: false,
 			highlighted_dict_index: None,
-			dict_options: vec![Dictionary::Moby, Dictionary::Gwicks],
+			dict_options: DictionaryRegistry::global().dictionaries(),
+			is_editing_path: false,
+			path_input: Input::new(String::new()),
+			path_error: None,
 		}
 	}
 }
@@ -44,6 +52,94 @@ impl Settings {
 		None
 	}
 
+	fn load_custom_dictionary(&mut self, state: &mut AppState) {
+		let path = self.path_input.value().trim();
+
+		if path.is_empty() {
+			return;
+		}
+
+		let dict = Dictionary::custom(path);
+
+		if let Err(e) = self.select_dictionary(&dict, state) {
+			self.path_error = Some(e.to_string());
+			return;
+		}
+
+		self.highlighted_dict_index = self.get_dict_option_index(&dict);
+		self.path_error = None;
+		self.is_editing_path = false;
+		self.path_input.reset();
+	}
+
+	/// Select the dictionary saved by the last run. Keeps the default if it
+	/// can no longer be loaded, e.g. a word list file was moved.
+	pub fn restore_dictionary(&mut self, dict: &Dictionary, state: &mut AppState) {
+		let _ = self.select_dictionary(dict, state);
+	}
+
+	/// Register and load `dict`, then use it. A list whose name is taken is
+	/// never loaded, and one that fails to load is not left registered
+	fn select_dictionary(
+		&mut self,
+		dict: &Dictionary,
+		state: &mut AppState,
+	) -> Result<(), DataError> {
+		let registry = DictionaryRegistry::global();
+
+		registry.register(dict.clone())?;
+
+		if let Err(e) = registry.get(dict) {
+			registry.unregister(dict);
+			return Err(e);
+		}
+
+		self.dict_options = registry.dictionaries();
+		state.dictionary = dict.clone();
+
+		Ok(())
+	}
+
+	fn render_path_input(&self, area: Rect, buf: &mut Buffer, state: &mut AppState) {
+		let block = Block::bordered()
+			.padding(Padding::horizontal(1))
+			.title(" Load dictionary <f>ile ");
+		let [input_area, message_area] =
+			Layout::vertical([Length(1), Length(1)]).areas(block.inner(area));
+		let input_width = usize::from(input_area.width);
+
+		Paragraph::new(self.path_input.value())
+			.scroll((
+				0,
+				u16::try_from(self.path_input.visual_scroll(input_width)).unwrap_or_default(),
+			))
+			.style(if self.is_editing_path {
+				Style::default().fg(tailwind::BLUE.c600).bold()
+			} else {
+				Style::default()
+			})
+			.render(input_area, buf);
+
+		if let Some(error) = &self.path_error {
+			Paragraph::new(error.as_str())
+				.style(Style::default().fg(tailwind::RED.c500))
+				.render(message_area, buf);
+		} else {
+			Paragraph::new("path to a word list file or directory, one word per line")
+				.style(Style::default().dim())
+				.render(message_area, buf);
+		}
+
+		if self.is_editing_path {
+			let scroll = self.path_input.visual_scroll(input_width);
+			let x = u16::try_from(self.path_input.visual_cursor().max(scroll)).unwrap_or_default();
+
+			state.cursor_position = Some((input_area.x.saturating_add(x), input_area.y));
+		}
+
+		block.render(area, buf);
+	}
+
 	fn render_dictionary_select(&self, area: Rect, buf: &mut Buffer, state: &AppState) {
 		let block = Block::bordered()
 			.padding(Padding::horizontal(1))
@@ -85,11 +181,13 @@ impl AppTabIo for Settings {
 
 	fn set_active(&mut self, is_active: bool, state: &mut AppState) {
 		self.is_active = is_active;
+		self.is_editing_path = false;
 
 		if !is_active {
 			return;
 		}
 
+		self.dict_options = DictionaryRegistry::global().dictionaries();
 		self.highlighted_dict_index = self.get_dict_option_index(&state.dictionary);
 	}
 
@@ -99,7 +197,23 @@ impl AppTabIo for Settings {
 		}
 
 		if let Event::Key(key_event) = event {
+			if self.is_editing_path {
+				match key_event.code {
+					KeyCode::Esc => {
+						self.is_editing_path = false;
+						state.cursor_position = None;
+					}
+					KeyCode::Enter => self.load_custom_dictionary(state),
+					_ => {
+						self.path_input.handle_event(event);
+					}
+				}
+
+				return Ok(());
+			}
+
 			match key_event.code {
+				KeyCode::Char('f') => self.is_editing_path = true,
 				KeyCode::Tab => {
 					self.highlighted_dict_index = match self.highlighted_dict_index {
 						Some(index) => index
@@ -128,10 +242,14 @@ impl StatefulWidgetRef for Settings {
 	fn render_ref(&self, area: Rect, buf: &mut Buffer, state: &mut AppState) {
 		let block = Block::new().padding(Padding::uniform(1));
 		let len = u16::try_from(self.dict_options.len()).unwrap_or_default();
-		let [dict_area] =
-			Layout::vertical([Length(len.saturating_add(2))]).areas(block.inner(area));
+		let [dict_area, path_area, _] =
+			Layout::vertical([Length(len.saturating_add(2)), Length(4), Min(0)])
+				.areas(block.inner(area));
+
+		state.cursor_position = None;
 
 		self.render_dictionary_select(dict_area, buf, state);
+		self.render_path_input(path_area, buf, state);
 		block.render(area, buf);
 	}
 }
@@ -1,4 +1,5 @@
 mod app_tabs;
+mod session;
 mod state;
 mod widgets;
 
@@ -15,13 +16,16 @@ use ratatui::text::Line;
 use ratatui::widgets::{Block, Paragraph, StatefulWidget, StatefulWidgetRef, Tabs, Widget};
 use ratatui::{DefaultTerminal, Frame};
 
-use crate::app_tabs::{AppTab, AppTabIo, MatchWords, NotWordle, Settings, Tab};
+use crate::app_tabs::{AppTab, AppTabIo, MatchWords, MultiBoard, NotWordle, Play, Settings, Tab};
+use crate::session::Session;
 use crate::state::AppState;
 
 #[derive(Default, Debug)]
 pub struct App<'a> {
 	match_words: MatchWords<'a>,
 	not_wordle: NotWordle<'a>,
+	multi_board: MultiBoard<'a>,
+	play: Play,
 	settings: Settings,
 	selected_tab: Tab,
 	exit: bool,
@@ -34,11 +38,18 @@ enum EventHandledStatus {
 }
 
 impl App<'_> {
+	/// Run until quit, starting from the session saved by the last run and
+	/// saving it again on the way out.
+	///
 	/// # Errors
-	/// Propagates errors from `draw` and `handle_events`.
+	/// Propagates errors from `draw`, `handle_events` and `Session::save`.
 	pub fn run(&mut self, terminal: &mut DefaultTerminal) -> Result<()> {
 		let mut state = AppState::default();
 
+		if let Some(session) = Session::load() {
+			self.restore(&session, &mut state);
+		}
+
 		self.match_words.set_active(true, &mut state);
 
 		while !self.exit {
@@ -47,7 +58,21 @@ impl App<'_> {
 				.wrap_err("handle events failed")?;
 		}
 
-		Ok(())
+		self.session(&state).save().wrap_err("save session failed")
+	}
+
+	fn restore(&mut self, session: &Session, state: &mut AppState) {
+		self.settings.restore_dictionary(&session.dictionary, state);
+		self.match_words.restore(&session.match_words, state);
+		self.not_wordle.restore(&session.not_wordle, state);
+	}
+
+	fn session(&self, state: &AppState) -> Session {
+		Session {
+			dictionary: state.dictionary.clone(),
+			not_wordle: self.not_wordle.registered().clone(),
+			match_words: self.match_words.inputs(),
+		}
 	}
 
 	fn draw(&self, frame: &mut Frame, state: &mut AppState) {
@@ -73,6 +98,12 @@ impl App<'_> {
 			self.not_wordle
 				.handle_event(&received_event, state)
 				.wrap_err("not wordle: handle events failed")?;
+			self.multi_board
+				.handle_event(&received_event, state)
+				.wrap_err("multi board: handle events failed")?;
+			self.play
+				.handle_event(&received_event, state)
+				.wrap_err("play: handle events failed")?;
 			self.settings
 				.handle_event(&received_event, state)
 				.wrap_err("settings: handle events failed")?;
@@ -85,6 +116,8 @@ impl App<'_> {
 		match self.selected_tab {
 			Tab::MatchWords => &self.match_words,
 			Tab::NotWordle => &self.not_wordle,
+			Tab::MultiBoard => &self.multi_board,
+			Tab::Play => &self.play,
 			Tab::Settings => &self.settings,
 		}
 	}
@@ -112,36 +145,34 @@ impl App<'_> {
 	}
 
 	fn go_to_tab(&mut self, tab_num: usize, state: &mut AppState) {
-		match tab_num {
-			1 => {
-				state.cursor_position = None;
-				self.selected_tab = Tab::MatchWords;
-				self.match_words.set_active(true, state);
-				self.not_wordle.set_active(false, state);
-				self.settings.set_active(false, state);
-			}
-			2 => {
-				state.cursor_position = None;
-				self.selected_tab = Tab::NotWordle;
-				self.match_words.set_active(false, state);
-				self.not_wordle.set_active(true, state);
-				self.settings.set_active(false, state);
-			}
-			3 => {
-				state.cursor_position = None;
-				self.selected_tab = Tab::Settings;
-				self.match_words.set_active(false, state);
-				self.not_wordle.set_active(false, state);
-				self.settings.set_active(true, state);
-			}
-			_ => (),
-		}
+		let tab = match tab_num {
+			1 => Tab::MatchWords,
+			2 => Tab::NotWordle,
+			3 => Tab::MultiBoard,
+			4 => Tab::Play,
+			5 => Tab::Settings,
+			_ => return,
+		};
+
+		state.cursor_position = None;
+		self.match_words
+			.set_active(matches!(tab, Tab::MatchWords), state);
+		self.not_wordle
+			.set_active(matches!(tab, Tab::NotWordle), state);
+		self.multi_board
+			.set_active(matches!(tab, Tab::MultiBoard), state);
+		self.play.set_active(matches!(tab, Tab::Play), state);
+		self.settings
+			.set_active(matches!(tab, Tab::Settings), state);
+		self.selected_tab = tab;
 	}
 
 	fn render_header(&self, area: Rect, buf: &mut Buffer) {
 		let labels = vec![
 			self.match_words.label(),
 			self.not_wordle.label(),
+			self.multi_board.label(),
+			self.play.label(),
 			self.settings.label(),
 		]
 		.into_iter()
@@ -151,7 +182,9 @@ impl App<'_> {
 		let selected_tab_index = match &self.selected_tab {
 			Tab::MatchWords => 0,
 			Tab::NotWordle => 1,
-			Tab::Settings => 2,
+			Tab::MultiBoard => 2,
+			Tab::Play => 3,
+			Tab::Settings => 4,
 		};
 		let block = Block::bordered()
 			.title(Line::from(" WRD ".bold()))
@@ -192,6 +225,8 @@ impl App<'_> {
 		match self.selected_tab {
 			Tab::MatchWords => self.match_words.render_ref(content_area, buf, state),
 			Tab::NotWordle => self.not_wordle.render_ref(content_area, buf, state),
+			Tab::MultiBoard => self.multi_board.render_ref(content_area, buf, state),
+			Tab::Play => self.play.render_ref(content_area, buf, state),
 			Tab::Settings => self.settings.render_ref(content_area, buf, state),
 		}
 
@@ -0,0 +1,52 @@
+use std::path::PathBuf;
+use std::{env, fs};
+
+use color_eyre::Result;
+use serde::{Deserialize, Serialize};
+use wrd_lib::{Dictionary, Notwordle};
+
+/// What is kept between runs, in `~/.wrd_session.json`. `wrd nw --session`
+/// reads the dictionary and Not wordle guesses from the same file
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Session {
+	pub dictionary: Dictionary,
+	#[serde(flatten)]
+	pub not_wordle: Notwordle,
+	pub match_words: MatchInputs,
+}
+
+/// The Match tab inputs, as typed
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MatchInputs {
+	pub pattern: String,
+	pub within: String,
+	pub include: String,
+	pub exclude: String,
+}
+
+impl Session {
+	fn path() -> Option<PathBuf> {
+		env::var_os("HOME").map(|home| PathBuf::from(home).join(".wrd_session.json"))
+	}
+
+	/// The session saved by the last run, if there is one that can be read
+	pub fn load() -> Option<Self> {
+		let json = fs::read_to_string(Self::path()?).ok()?;
+
+		// an unreadable session is dropped rather than keeping the app from
+		// starting, it is overwritten on exit
+		serde_json::from_str(&json).ok()
+	}
+
+	/// # Errors
+	/// Errors if the session file can not be written.
+	pub fn save(&self) -> Result<()> {
+		let Some(path) = Self::path() else {
+			return Ok(());
+		};
+
+		fs::write(path, serde_json::to_string_pretty(self)?)?;
+
+		Ok(())
+	}
+}
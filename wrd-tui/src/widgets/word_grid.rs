@@ -5,15 +5,29 @@ use ratatui::widgets::{Row, Table, Widget, WidgetRef};
 
 const COLUMNS: usize = 10;
 
-#[derive(Default, Debug, Clone)]
+#[derive(Debug, Clone)]
 pub struct WordGrid<'a> {
 	rows: Vec<Row<'a>>,
+	columns: usize,
+}
+
+impl Default for WordGrid<'_> {
+	fn default() -> Self {
+		Self::with_columns(COLUMNS)
+	}
 }
 
 impl WordGrid<'_> {
+	pub const fn with_columns(columns: usize) -> Self {
+		Self {
+			rows: vec![],
+			columns,
+		}
+	}
+
 	pub fn update(&mut self, words: &[String]) {
 		self.rows = words
-			.chunks(COLUMNS)
+			.chunks(self.columns.max(1))
 			.map(|row| Row::new::<Vec<String>>(row.into()))
 			.collect();
 	}
@@ -21,7 +35,7 @@ impl WordGrid<'_> {
 
 impl WidgetRef for WordGrid<'_> {
 	fn render_ref(&self, area: Rect, buf: &mut Buffer) {
-		let widths = (0..COLUMNS).map(|_| Min(0));
+		let widths = (0..self.columns).map(|_| Min(0));
 
 		Table::new(self.rows.clone(), widths)
 			.column_spacing(1)
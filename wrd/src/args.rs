@@ -0,0 +1,400 @@
+use std::error::Error;
+use std::path::PathBuf;
+
+use clap::{Args, ValueEnum};
+use wrd_lib::{Dictionary, GuessPool, SortKey, WithinMode};
+
+use crate::{
+	MatchOutput, NotwordleOutput, OutputFormat, Session, build_matcher, explain_match_runner,
+	explain_notwordle_runner, isomorph_runner, match_words_runner, multi_notwordle_runner,
+	notwordle_runner,
+};
+
+#[derive(Args, Debug)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct MwArgs {
+	/// matches lowercase ascii only. character positions seperated by space
+	///
+	/// wrd mw -p '** * ae !bcd **'
+	///
+	/// - **: match any number of any character{n}
+	/// - *: match any single character{n}
+	/// - abc: match any of these chars (1 or more) in this position{n}
+	/// - !abc: exclude any of these chars (1 or more) in this position{n}
+	/// - a-f: a range of chars, can be mixed with other chars: a-fxz, !a-f{n}
+	/// - "ing": match these chars in order, as a run of positions{n}
+	/// - ?: make the previous position optional: *?, s?, "ed"?{n}
+	/// - e{2}, e{2,}, e{2,4}: repeat the previous position exactly 2 times,
+	///   2 or more times, or 2 to 4 times: *{2,4}, "ab"{2}{n}
+	/// - V, C: any vowel or consonant, can be mixed with other chars: Vy,
+	///   !C{n}
+	/// - @name: a letter set defined with --set, or in the pattern with
+	///   @name=letters
+	///
+	/// e.g.{n}
+	/// - match any word starting with "y" and "e"{n}
+	///   wrd mw -p 'y e **'
+	///
+	/// - match any word ending with "ry" or "ny"{n}
+	///   wrd mw -p '** rn y'
+	///
+	/// - match a five character word with "r" as 2nd char, "n" or "t"
+	///   as 4th char, and not "s" or "y" as the last character{n}
+	///   wrd mw -p '* r * nt !sy'
+	///
+	/// - match words of 4 to 6 characters ending in "ing"{n}
+	///   wrd mw -p '*{1,3} "ing"'
+	///
+	/// - match words starting with a letter from "a" to "f", with an
+	///   optional "s" at the end{n}
+	///   wrd mw -p 'a-f ** "ed" s?'
+	///
+	/// - match five letter words alternating consonants and vowels{n}
+	///   wrd mw -p 'C V C V C'
+	///
+	/// - match words starting with a rare letter{n}
+	///   wrd mw -p '@rare=jqxz @rare **'
+	#[arg(short, long)]
+	pattern: String,
+
+	/// define a letter set usable as @name in the pattern, --within,
+	/// --include and --exclude. can be repeated
+	///
+	/// letters can use ranges, V, C and sets defined before them
+	///
+	/// e.g.{n}
+	/// - find words with a rare letter and no other consonants{n}
+	///   wrd mw -p '**' --set rare=jqxz -i '@rare' -w 'V@rare' -r
+	#[arg(long = "set", value_name = "NAME=LETTERS")]
+	sets: Vec<String>,
+
+	/// read the pattern as letter variables, one character per letter
+	///
+	/// - A-Z: a variable, the same variable is the same letter wherever
+	///   it is used{n}
+	/// - a-z: this letter{n}
+	/// - *: any letter
+	///
+	/// e.g.{n}
+	/// - match words like "that" and "noun"{n}
+	///   wrd mw -p 'ABCA' -l
+	///
+	/// - match five letter words starting and ending with the same
+	///   letter and with a "t" in the middle{n}
+	///   wrd mw -p 'A*t*A' -l
+	#[arg(
+		short,
+		long,
+		conflicts_with_all = ["within", "include", "exclude", "sets"]
+	)]
+	letter_vars: bool,
+
+	/// with --letter-vars, different variables must be different letters
+	#[arg(long, requires = "letter_vars")]
+	distinct: bool,
+
+	/// show at most this many matches, stopping the search once found
+	#[arg(short = 'n', long)]
+	limit: Option<usize>,
+
+	/// show the number of matches instead of the matches
+	///
+	/// with --limit, counting stops at the limit, e.g. to check there
+	/// are at least 10 matches{n}
+	///   wrd mw -p 'C V **' -c -n 10
+	#[arg(short, long)]
+	count: bool,
+
+	/// order matches, rather than in dictionary order
+	///
+	/// sorting finds every match before applying --limit. score compares
+	/// each match with every other, so is slow for broad patterns
+	#[arg(long, value_enum)]
+	sort: Option<SortArg>,
+
+	/// leave out words used fewer times than this, see --frequencies
+	#[arg(short, long, value_name = "COUNT")]
+	min_frequency: Option<u64>,
+
+	/// how to print matches, e.g. lines or json to pipe into other tools
+	#[arg(long, value_enum, default_value = "grid")]
+	format: OutputFormat,
+
+	/// explain why a word does or does not match, instead of listing
+	/// matches
+	///
+	/// e.g.{n}
+	///   wrd mw -p '* r * nt !sy' --why brand
+	#[arg(long, value_name = "WORD", conflicts_with_all = ["letter_vars", "count", "limit", "sort"])]
+	why: Option<String>,
+
+	/// words can only contain characters within this group
+	///
+	/// V, C and @name sets add all their characters to the group
+	///
+	/// each character can be used as many times as it appears in the group,
+	/// like tiles in a scrabble rack (see --repeat-within)
+	///
+	/// e.g.{n}
+	/// - find all imperfect anagrams{n}
+	///   wrd mw -p '**' -w 'theword'
+	#[arg(short, long, default_value_t = ("").to_string())]
+	within: String,
+
+	/// allow characters in --within to be used any number of times
+	///
+	/// e.g.{n}
+	/// - find words using only "t", "h", "e", "r"{n}
+	///   wrd mw -p '**' -w 'ther' -r
+	#[arg(short, long)]
+	repeat_within: bool,
+
+	/// word must include all of these characters
+	///
+	/// repeated characters must appear at least as many times in the word
+	///
+	/// e.g.{n}
+	/// - find all perfect anagrams{n}
+	///   wrd mw -p '* * * * * *' -i 'miters'
+	///
+	/// - find all permutations with at least 4 characters{n}
+	///   and containing a key character{n}
+	///   wrd mw -p '* * * * **' -i 'd' -w 'abcdefg'
+	///
+	/// - find all words with at least two "l"s{n}
+	///   wrd mw -p '**' -i 'll'
+	///
+	/// - find all words with at least three vowels, V for a vowel and C
+	///   for a consonant{n}
+	///   wrd mw -p '**' -i 'VVV'
+	#[arg(short, long, default_value_t = ("").to_string())]
+	include: String,
+
+	/// words must not include any of these characters
+	///
+	/// V, C and @name sets exclude all their characters
+	#[arg(short, long, default_value_t = ("").to_string())]
+	exclude: String,
+}
+
+impl MwArgs {
+	/// Print the words matching.
+	///
+	/// # Errors
+	/// Propagates errors from `build_matcher`, `match_words_runner` and
+	/// `isomorph_runner`.
+	pub fn run(&self, dictionary: &Dictionary) -> Result<(), Box<dyn Error>> {
+		let within_mode = if self.repeat_within {
+			WithinMode::Repeat
+		} else {
+			WithinMode::Rack
+		};
+		let output = MatchOutput {
+			limit: self.limit,
+			count: self.count,
+			sort: self.sort.map(SortKey::from).unwrap_or_default(),
+			min_frequency: self.min_frequency,
+			format: self.format,
+		};
+
+		if self.letter_vars {
+			return isomorph_runner(&self.pattern, self.distinct, output, dictionary);
+		}
+
+		let matcher = build_matcher(
+			&self.pattern,
+			&self.include,
+			&self.exclude,
+			&self.within,
+			within_mode,
+			&self.sets,
+		)?;
+
+		if let Some(word) = &self.why {
+			return explain_match_runner(matcher, word, self.min_frequency, dictionary);
+		}
+
+		match_words_runner(matcher, output, dictionary)
+	}
+}
+
+#[derive(Args, Debug)]
+pub struct NwArgs {
+	/// guess results: comma seperated list of guess results
+	///
+	/// a guess result is a space seperated list of the results of a guess:{n}
+	/// - single a-z: letter in correct position{n}
+	/// - ? + single a-z: letter in word but in wrong position{n}
+	/// - ! + single a-z: letter not in word{n}
+	///
+	/// e.g. encoding the result of the guess "plate" where:{n}
+	/// - 'p' is in correct position{n}
+	/// - 'l' is in word but in wrong position{n}
+	/// - 'a' is not in word{n}
+	/// - 't' is in word but in wrong position{n}
+	/// - 'e' is in correct position{n}
+	///
+	/// wrd nw 'p ?l !a ?t e'
+	///
+	/// to see words remaning from compounding guesses, provide a comma
+	/// seperated list of results:
+	///
+	/// wrd nw 'p ?l !a ?t e,p !o l ?t e'
+	///
+	/// results can also be written as:{n}
+	/// - word and colours, g: right, y: wrong position, .: not in word{n}
+	///   wrd nw -g 'plate:gy.yg'{n}
+	/// - word and emoji, as shared from a game{n}
+	///   wrd nw -g 'plate 🟩🟨⬛🟨🟩'{n}
+	/// - case shorthand, uppercase: right, ?: wrong position,
+	///   lowercase: not in word{n}
+	///   wrd nw -g 'P?la?tE'
+	///
+	/// results can be separated by new lines as well as commas
+	#[arg(short, long, required_unless_present = "session")]
+	guess_results: Option<String>,
+
+	/// start from the guess results saved in a session file, e.g. by
+	/// wrd-tui, then add any given with --guess-results
+	///
+	/// the session's dictionary is used unless --dictionary is given
+	///
+	/// e.g.{n}
+	///   wrd nw --session game.json -g 'c r !a n e'
+	#[arg(long, value_name = "PATH", conflicts_with = "boards")]
+	session: Option<PathBuf>,
+
+	/// number of boards, for multi-board variants like quordle
+	///
+	/// every guess is played on all boards: give a result for each board,
+	/// seperated by "|". results for solved boards are left empty. if
+	/// the first result has a word, the others can be colours alone
+	///
+	/// e.g.{n}
+	///   wrd nw -b 4 -g 'crane:g....|.y...|..y.y|.....'
+	#[arg(short, long, default_value_t = 1)]
+	boards: usize,
+
+	/// suggest the next guess: list the N guesses expected to narrow down
+	/// the remaining words the most (default 10)
+	///
+	/// e.g.{n}
+	///   wrd nw -g 'p ?l !a ?t e' --suggest 5
+	#[arg(short, long, num_args = 0..=1, default_missing_value = "10")]
+	suggest: Option<usize>,
+
+	/// suggest from any dictionary word of the right length, including
+	/// words already ruled out, rather than only the remaining words
+	#[arg(short = 'a', long, requires = "suggest")]
+	suggest_any: bool,
+
+	/// order the remaining words, rather than in dictionary order
+	#[arg(long, value_enum)]
+	sort: Option<SortArg>,
+
+	/// leave out words used fewer times than this, see --frequencies
+	#[arg(short, long, value_name = "COUNT")]
+	min_frequency: Option<u64>,
+
+	/// how to print the results
+	///
+	/// json has every guess result with the words left after it, the final
+	/// words and any suggestions. lines and csv have the final words alone
+	#[arg(long, value_enum, default_value = "grid")]
+	format: OutputFormat,
+
+	/// explain which guess result ruled out a word and why, instead of
+	/// listing the words left
+	///
+	/// e.g.{n}
+	///   wrd nw -g 'p ?l !a ?t e' --why pilot
+	#[arg(long, value_name = "WORD", conflicts_with_all = ["boards", "suggest", "sort"])]
+	why: Option<String>,
+}
+
+impl NwArgs {
+	/// Print the words left after the guess results.
+	///
+	/// # Errors
+	/// Propagates errors from `Session::load`, `notwordle_runner` and
+	/// `multi_notwordle_runner`.
+	pub fn run(&self, dictionary: &Dictionary) -> Result<(), Box<dyn Error>> {
+		let output = NotwordleOutput {
+			suggest: self.suggest,
+			suggest_pool: if self.suggest_any {
+				GuessPool::Dictionary
+			} else {
+				GuessPool::Remaining
+			},
+			sort: self.sort.map(SortKey::from).unwrap_or_default(),
+			min_frequency: self.min_frequency,
+			format: self.format,
+		};
+
+		let guess_results = self.all_guess_results()?;
+
+		if let Some(word) = &self.why {
+			return explain_notwordle_runner(&guess_results, word, self.min_frequency, dictionary);
+		}
+
+		if self.boards > 1 {
+			multi_notwordle_runner(&guess_results, self.boards, output, dictionary)
+		} else {
+			notwordle_runner(&guess_results, output, dictionary)
+		}
+	}
+
+	/// The dictionary saved in the --session file, if there is one.
+	///
+	/// # Errors
+	/// Propagates errors from `Session::load`.
+	pub fn session_dictionary(&self) -> Result<Option<Dictionary>, Box<dyn Error>> {
+		Ok(self
+			.session
+			.as_deref()
+			.map(Session::load)
+			.transpose()?
+			.and_then(|session| session.dictionary))
+	}
+
+	/// The --session guess results followed by the --guess-results ones
+	fn all_guess_results(&self) -> Result<String, Box<dyn Error>> {
+		let saved = self.session.as_deref().map(Session::load).transpose()?;
+		let rows: Vec<String> = saved
+			.iter()
+			.flat_map(Session::guess_results)
+			.chain(self.guess_results.clone())
+			.collect();
+
+		Ok(rows.join(","))
+	}
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum SortArg {
+	/// alphabetical order
+	Alpha,
+	/// fewest letters first
+	Shortest,
+	/// most letters first
+	Longest,
+	/// highest scrabble score first
+	Scrabble,
+	/// most common first, for dictionaries with word frequencies
+	Frequency,
+	/// most informative as a wordle guess first
+	Score,
+}
+
+impl From<SortArg> for SortKey {
+	fn from(value: SortArg) -> Self {
+		match value {
+			SortArg::Alpha => Self::Alphabetical,
+			SortArg::Shortest => Self::Shortest,
+			SortArg::Longest => Self::Longest,
+			SortArg::Scrabble => Self::Scrabble,
+			SortArg::Frequency => Self::Frequency,
+			SortArg::Score => Self::Score,
+		}
+	}
+}
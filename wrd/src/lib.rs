@@ -1,43 +1,630 @@
 use std::error::Error;
+use std::io::{self, Write};
+use std::slice;
+use std::sync::Arc;
 
 use colored::Colorize;
-use wrd_lib::{GuessResultToken, Notwordle, match_words};
+use wrd_lib::{
+	BenchOptions, Contradiction, DataError, Dictionary, DictionaryRegistry, FailedConstraint, Game,
+	GameOptions, GameStatus, GuessPool, GuessResultToken, GuessTile, LetterSets, Matcher,
+	MultiNotwordle, Notwordle, NotwordleError, SecretWord, SortKey, Suggestion, WithinMode,
+	WordFrequencies, bench, get_dictionary, match_isomorph, sort_words, tokenize_guess_result,
+};
 
+/// Resolve a dictionary argument: either the name of a registered dictionary
+/// or a path to a word list file (or directory of word list files).
+#[must_use]
+pub fn parse_dictionary_arg(value: &str) -> Dictionary {
+	DictionaryRegistry::global()
+		.find(value)
+		.unwrap_or_else(|| Dictionary::custom(value))
+}
+
+mod args;
+mod output;
+mod repl;
+mod session;
+
+pub use crate::args::{MwArgs, NwArgs};
+pub use crate::output::OutputFormat;
+use crate::output::{
+	GuessRow, format_boards, format_count, format_multi_notwordle_json, format_notwordle_json,
+	format_words,
+};
+pub use crate::repl::repl_runner;
+pub use crate::session::Session;
+
+/// How many matches to print, in what order and how
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MatchOutput {
+	/// print at most this many matches
+	pub limit: Option<usize>,
+	/// print the number of matches instead of the matches
+	pub count: bool,
+	/// order to print matches in
+	pub sort: SortKey,
+	/// leave out words used fewer times than this
+	pub min_frequency: Option<u64>,
+	pub format: OutputFormat,
+}
+
+/// What to print after the words left by guesses
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NotwordleOutput {
+	/// suggest this many next guesses
+	pub suggest: Option<usize>,
+	/// where suggested guesses come from
+	pub suggest_pool: GuessPool,
+	/// order to print the words left in
+	pub sort: SortKey,
+	/// leave out words used fewer times than this
+	pub min_frequency: Option<u64>,
+	pub format: OutputFormat,
+}
+
+/// Compile a pattern for `match_words_runner`. `sets` are letter set
+/// definitions, `name=letters`, usable as `@name`.
+///
 /// # Errors
-/// Propagates errors from `match_words`.
-pub fn match_words_runner(
+/// Propagates errors from `LetterSets::define` and `Matcher::with_sets`.
+pub fn build_matcher(
 	pattern: &str,
 	include: &str,
 	exclude: &str,
 	within: &str,
+	within_mode: WithinMode,
+	sets: &[String],
+) -> Result<Matcher, Box<dyn Error>> {
+	let mut letter_sets = LetterSets::default();
+
+	for set in sets {
+		letter_sets.define(set)?;
+	}
+
+	Ok(Matcher::with_sets(
+		pattern,
+		include,
+		exclude,
+		within,
+		within_mode,
+		&letter_sets,
+	)?)
+}
+
+/// Print the words matching. Stops looking once `output.limit` words are
+/// found, unless they need sorting first.
+///
+/// # Errors
+/// Errors if `output.min_frequency` is set and the dictionary has no word
+/// frequencies.
+/// Propagates errors from `get_dictionary`.
+pub fn match_words_runner(
+	matcher: Matcher,
+	output: MatchOutput,
+	dictionary: &Dictionary,
+) -> Result<(), Box<dyn Error>> {
+	let dict = get_dictionary(dictionary)?;
+	let matcher = match output.min_frequency {
+		Some(min) => matcher.with_min_frequency(dictionary_frequencies(dictionary)?, min),
+		None => matcher,
+	};
+
+	match output.limit {
+		Some(limit) if output.sort == SortKey::Dictionary => {
+			print_matches(matcher.matches(dict).take(limit), output);
+		}
+		_ => {
+			let mut found = matcher.find_all(dict);
+
+			sort_words(&mut found, output.sort, dictionary);
+			print_matches(found.into_iter(), output);
+		}
+	}
+
+	Ok(())
+}
+
+/// Print the checks `word` fails against a compiled pattern, see
+/// `Matcher::explain`.
+///
+/// # Errors
+/// Errors if `min_frequency` is set and the dictionary has no word
+/// frequencies.
+/// Propagates errors from `get_dictionary`.
+pub fn explain_match_runner(
+	matcher: Matcher,
+	word: &str,
+	min_frequency: Option<u64>,
+	dictionary: &Dictionary,
 ) -> Result<(), Box<dyn Error>> {
-	let result = match_words(pattern, include, exclude, within, None)?;
+	let dict = get_dictionary(dictionary)?;
+	let word = word.to_lowercase();
+	let matcher = match min_frequency {
+		Some(min) => matcher.with_min_frequency(dictionary_frequencies(dictionary)?, min),
+		None => matcher,
+	};
 
-	println!("{}", format_word_grid(&result));
+	print_explanation(&word, &matcher.explain(&word), dict);
 
 	Ok(())
 }
 
+/// Print the first guess result that ruled out `word`, then every
+/// constraint it fails once all the guess results are in, see
+/// `Notwordle::explain`.
+///
 /// # Errors
-/// Propagates errors from `Notwordle::register_guess_result` and `Notwordle::refine`.
-pub fn notwordle_runner(guess_results: &str) -> Result<(), Box<dyn Error>> {
+/// Errors if `min_frequency` is set and the dictionary has no word
+/// frequencies.
+/// Propagates errors from `get_dictionary`, `Notwordle::register_guess_result`
+/// and `Notwordle::explain`.
+pub fn explain_notwordle_runner(
+	guess_results: &str,
+	word: &str,
+	min_frequency: Option<u64>,
+	dictionary: &Dictionary,
+) -> Result<(), Box<dyn Error>> {
+	let dict = get_dictionary(dictionary)?;
+	let word = word.to_lowercase();
 	let mut notwordle = Notwordle::default();
-	let results: Vec<&str> = guess_results.split(',').collect();
-	let mut print_items: Vec<&str> = vec![];
+	let mut ruled_out_by: Option<(usize, Vec<GuessResultToken>)> = None;
+	let results = guess_results
+		.split([',', '\n'])
+		.filter(|result| !result.trim().is_empty());
 
-	for result in results {
-		let tokenized = notwordle.register_guess_result(result)?;
-		let refined = notwordle.refine(None)?;
+	for (index, result) in results.enumerate() {
+		let tokenized = register_or_explain(&mut notwordle, result)?;
+
+		if ruled_out_by.is_none() && !notwordle.explain(&word)?.is_empty() {
+			ruled_out_by = Some((index, tokenized));
+		}
+	}
+
+	let mut failed = notwordle.explain(&word)?;
+
+	if let Some(min) = min_frequency
+		&& !dictionary_frequencies(dictionary)?.is_common(&word, min)
+	{
+		failed.push(FailedConstraint::Uncommon { min });
+	}
 
+	if let Some((index, tokenized)) = ruled_out_by {
 		println!(
-			"{} remaining after {}",
-			refined.len(),
+			"ruled out by guess result {} {}",
+			index.saturating_add(1),
 			format_notwordle_guess_result(&tokenized)
 		);
-		print_items = refined;
 	}
 
-	println!("{}", format_word_grid(&print_items));
+	print_explanation(&word, &failed, dict);
+
+	Ok(())
+}
+
+fn print_explanation(word: &str, failed: &[FailedConstraint], dict: &[&str]) {
+	if !dict.contains(&word) {
+		println!("{} is not in the dictionary", word.bright_yellow());
+	}
+
+	if failed.is_empty() {
+		println!("{} passes every constraint", word.bright_yellow());
+	} else {
+		println!("{} fails:", word.bright_yellow());
+
+		for constraint in failed {
+			println!("- {constraint}");
+		}
+	}
+}
+
+/// Match words against a letter variable pattern like `ABCA`.
+///
+/// # Errors
+/// Errors if `output.min_frequency` is set and the dictionary has no word
+/// frequencies.
+/// Propagates errors from `get_dictionary` and `match_isomorph`.
+pub fn isomorph_runner(
+	pattern: &str,
+	distinct: bool,
+	output: MatchOutput,
+	dictionary: &Dictionary,
+) -> Result<(), Box<dyn Error>> {
+	let dict = get_dictionary(dictionary)?;
+	let mut result = match_isomorph(pattern, distinct, Some(dict))?;
+
+	retain_common(&mut result, output.min_frequency, dictionary)?;
+	sort_words(&mut result, output.sort, dictionary);
+	print_matches(result.into_iter(), output);
+
+	Ok(())
+}
+
+/// The word frequencies loaded for a dictionary
+fn dictionary_frequencies(dictionary: &Dictionary) -> Result<Arc<WordFrequencies>, DataError> {
+	DictionaryRegistry::global()
+		.frequencies(dictionary)
+		.ok_or_else(|| DataError::NoFrequencies(dictionary.name().to_string()))
+}
+
+/// Leave out words used fewer than `min_frequency` times
+fn retain_common(
+	words: &mut Vec<&str>,
+	min_frequency: Option<u64>,
+	dictionary: &Dictionary,
+) -> Result<(), DataError> {
+	if let Some(min) = min_frequency {
+		let frequencies = dictionary_frequencies(dictionary)?;
+
+		words.retain(|word| frequencies.is_common(word, min));
+	}
+
+	Ok(())
+}
+
+fn print_matches<'a>(matches: impl Iterator<Item = &'a str>, output: MatchOutput) {
+	let matches = matches.take(output.limit.unwrap_or(usize::MAX));
+
+	if output.count {
+		println!("{}", format_count(matches.count(), output.format));
+	} else {
+		println!(
+			"{}",
+			format_words(&matches.collect::<Vec<_>>(), output.format)
+		);
+	}
+}
+
+/// Print the words each ciphertext word could decode to.
+///
+/// # Errors
+/// Propagates errors from `DictionaryRegistry::isomorph_index`.
+pub fn crypt_runner(ciphertext: &str, dictionary: &Dictionary) -> Result<(), Box<dyn Error>> {
+	let index = DictionaryRegistry::global().isomorph_index(dictionary)?;
+	let words = ciphertext
+		.split_whitespace()
+		.map(|word| {
+			word.chars()
+				.filter(char::is_ascii_alphabetic)
+				.collect::<String>()
+				.to_lowercase()
+		})
+		.filter(|word| !word.is_empty());
+
+	for word in words {
+		let candidates: Vec<&str> = index.candidates(&word).collect();
+
+		println!(
+			"{} {}",
+			word.bright_yellow(),
+			format!("{} possible", candidates.len()).dimmed()
+		);
+		println!("{}\n", format_word_grid(&candidates));
+	}
+
+	Ok(())
+}
+
+/// Print the words left after each guess result, then the final words in
+/// `output.sort` order. Formats other than grid and json only print the final
+/// words.
+///
+/// # Errors
+/// Errors if `output.min_frequency` is set and the dictionary has no word
+/// frequencies.
+/// Propagates errors from `get_dictionary`, `Notwordle::register_guess_result`,
+/// `Notwordle::refine` and `Notwordle::suggest`.
+pub fn notwordle_runner(
+	guess_results: &str,
+	output: NotwordleOutput,
+	dictionary: &Dictionary,
+) -> Result<(), Box<dyn Error>> {
+	let dict = get_dictionary(dictionary)?;
+	let mut notwordle = Notwordle::default();
+	let results: Vec<&str> = guess_results
+		.split([',', '\n'])
+		.filter(|result| !result.trim().is_empty())
+		.collect();
+	let mut steps: Vec<(Vec<GuessResultToken>, usize)> = vec![];
+	let mut remaining: Vec<&str> = vec![];
+
+	for result in results {
+		let tokenized = register_or_explain(&mut notwordle, result)?;
+
+		remaining = notwordle.refine(Some(dict))?;
+		retain_common(&mut remaining, output.min_frequency, dictionary)?;
+		steps.push((tokenized, remaining.len()));
+	}
+
+	sort_words(&mut remaining, output.sort, dictionary);
+
+	let frequencies = DictionaryRegistry::global().frequencies(dictionary);
+	let suggestions = output
+		.suggest
+		.map(|limit| {
+			notwordle.suggest(
+				Some(dict),
+				frequencies.as_deref(),
+				output.suggest_pool,
+				limit,
+			)
+		})
+		.transpose()?;
+
+	match output.format {
+		OutputFormat::Grid => {
+			for (tokenized, count) in &steps {
+				println!(
+					"{count} remaining after {}",
+					format_notwordle_guess_result(tokenized)
+				);
+			}
+
+			println!("{}", format_word_grid(&remaining));
+
+			if let Some(suggestions) = suggestions {
+				println!("\nsuggested guesses:\n{}", format_suggestions(&suggestions));
+			}
+		}
+		OutputFormat::Json => {
+			let guesses = steps
+				.iter()
+				.map(|(tokenized, count)| GuessRow::new(tokenized, *count))
+				.collect();
+
+			println!(
+				"{}",
+				format_notwordle_json(guesses, &remaining, suggestions.as_deref())
+			);
+		}
+		format => println!("{}", format_words(&remaining, format)),
+	}
+
+	Ok(())
+}
+
+/// Like `notwordle_runner` for multi-board variants. Each guess result is a `|`
+/// separated list of results, one per board.
+///
+/// # Errors
+/// Errors if `output.min_frequency` is set and the dictionary has no word
+/// frequencies.
+/// Propagates errors from `get_dictionary`,
+/// `MultiNotwordle::register_guess_row`, `MultiNotwordle::refine` and
+/// `MultiNotwordle::suggest`.
+pub fn multi_notwordle_runner(
+	guess_results: &str,
+	boards: usize,
+	output: NotwordleOutput,
+	dictionary: &Dictionary,
+) -> Result<(), Box<dyn Error>> {
+	let dict = get_dictionary(dictionary)?;
+	let mut notwordle = MultiNotwordle::new(boards);
+	let rows = guess_results
+		.split([',', '\n'])
+		.filter(|row| !row.trim().is_empty());
+	let mut steps: Vec<BoardsStep> = vec![];
+
+	for row in rows {
+		let tokenized = notwordle.register_guess_row(row)?;
+		let mut counts = vec![];
+
+		for mut remaining in notwordle.refine(Some(dict))? {
+			retain_common(&mut remaining, output.min_frequency, dictionary)?;
+			counts.push(remaining.len());
+		}
+
+		steps.push((tokenized, counts));
+	}
+
+	let mut remaining_boards: Vec<(Vec<&str>, bool)> = vec![];
+
+	for (mut remaining, solved) in notwordle
+		.refine(Some(dict))?
+		.into_iter()
+		.zip(notwordle.solved())
+	{
+		retain_common(&mut remaining, output.min_frequency, dictionary)?;
+		sort_words(&mut remaining, output.sort, dictionary);
+		remaining_boards.push((remaining, solved));
+	}
+
+	let frequencies = DictionaryRegistry::global().frequencies(dictionary);
+	let suggestions = output
+		.suggest
+		.map(|limit| {
+			notwordle.suggest(
+				Some(dict),
+				frequencies.as_deref(),
+				output.suggest_pool,
+				limit,
+			)
+		})
+		.transpose()?;
+
+	match output.format {
+		OutputFormat::Grid => {
+			print_multi_notwordle_grid(&steps, &remaining_boards, suggestions.as_deref());
+		}
+		OutputFormat::Json => {
+			let guesses = steps
+				.iter()
+				.map(|(tokenized, counts)| {
+					tokenized
+						.iter()
+						.zip(counts)
+						.map(|(result, count)| {
+							result.as_ref().map(|result| GuessRow::new(result, *count))
+						})
+						.collect()
+				})
+				.collect();
+
+			println!(
+				"{}",
+				format_multi_notwordle_json(guesses, &remaining_boards, suggestions.as_deref())
+			);
+		}
+		format => println!("{}", format_boards(&remaining_boards, format)),
+	}
+
+	Ok(())
+}
+
+/// A guess row's results on each board, `None` for solved boards, and the
+/// number of words left on each board after it
+type BoardsStep = (Vec<Option<Vec<GuessResultToken>>>, Vec<usize>);
+
+fn print_multi_notwordle_grid(
+	steps: &[BoardsStep],
+	boards: &[(Vec<&str>, bool)],
+	suggestions: Option<&[Suggestion]>,
+) {
+	for (tokenized, counts) in steps {
+		let summary: Vec<String> = tokenized
+			.iter()
+			.zip(counts)
+			.map(|(result, count)| {
+				result.as_ref().map_or_else(
+					|| format!("{:<width$}", "-", width = result_width(tokenized)),
+					|result| {
+						format!(
+							"{} {}",
+							format_notwordle_guess_result(result),
+							format!("{count:<5}").dimmed()
+						)
+					},
+				)
+			})
+			.collect();
+
+		println!("{}", summary.join("  "));
+	}
+
+	for (index, (remaining, solved)) in boards.iter().enumerate() {
+		let board = index.saturating_add(1);
+
+		if *solved {
+			println!("\nboard {board}: {}", "solved".blue());
+		} else {
+			println!("\nboard {board}: {} remaining", remaining.len());
+			println!("{}", format_word_grid(remaining));
+		}
+	}
+
+	if let Some(suggestions) = suggestions {
+		println!("\nsuggested guesses:\n{}", format_suggestions(suggestions));
+	}
+}
+
+/// Play a game of wordle on the command line, reading guesses from stdin.
+///
+/// # Errors
+/// Propagates errors from `get_dictionary`, `Game::new` and reading stdin.
+pub fn play_runner(
+	secret: SecretWord,
+	options: GameOptions,
+	dictionary: &Dictionary,
+) -> Result<(), Box<dyn Error>> {
+	let dict = get_dictionary(dictionary)?;
+	let mut game = Game::new(dict, secret, options)?;
+	let mut line = String::new();
+
+	println!(
+		"guess the {} letter word in {} tries{}",
+		options.word_length,
+		options.max_guesses,
+		if options.hard_mode {
+			" (hard mode)"
+		} else {
+			""
+		}
+	);
+
+	while game.status() == GameStatus::InProgress {
+		print!(
+			"{} ",
+			format!(
+				"{}/{}>",
+				game.guesses().len().saturating_add(1),
+				options.max_guesses
+			)
+			.dimmed()
+		);
+		io::stdout().flush()?;
+
+		line.clear();
+
+		if io::stdin().read_line(&mut line)? == 0 {
+			println!("\nthe word was {}", game.answer().bright_yellow());
+			return Ok(());
+		}
+
+		match game.guess(&line) {
+			Ok(result) => println!("     {}", format_notwordle_guess_result(result)),
+			Err(err) => println!("     {}", err.to_string().red()),
+		}
+	}
+
+	if game.status() == GameStatus::Won {
+		println!("\nsolved!");
+	} else {
+		println!("\nthe word was {}", game.answer().bright_yellow());
+	}
+
+	println!("\n{}", game.share_grid());
+
+	Ok(())
+}
+
+/// Solve every word of a length in the dictionary and print how many guesses
+/// it took.
+///
+/// # Errors
+/// Propagates errors from `get_dictionary` and `bench`.
+pub fn bench_runner(options: &BenchOptions, dictionary: &Dictionary) -> Result<(), Box<dyn Error>> {
+	let dict = get_dictionary(dictionary)?;
+	let report = bench(dict, options)?;
+	let histogram = report.histogram();
+	let most = histogram.values().max().copied().unwrap_or_default();
+	let failures = report.failures();
+
+	println!(
+		"{} games opening with {}\n",
+		report.games.len(),
+		report.opening.bright_yellow()
+	);
+	println!("mean\t{:.3}", report.mean());
+	println!("median\t{:.1}", report.median());
+	println!("max\t{}", report.max());
+	println!(
+		"failed\t{} {}\n",
+		failures.len(),
+		format!(
+			"({:.2}% over {} guesses)",
+			report.failure_rate() * 100.0,
+			report.max_guesses
+		)
+		.dimmed()
+	);
+
+	for (guesses, count) in &histogram {
+		let width = count
+			.saturating_mul(40)
+			.checked_div(most)
+			.unwrap_or_default();
+		let bar = "#".repeat(width.max(1));
+		let bar = if *guesses > report.max_guesses {
+			bar.red()
+		} else {
+			bar.blue()
+		};
+
+		println!("{guesses:>3}\t{bar} {}", count.to_string().dimmed());
+	}
+
+	if !failures.is_empty() {
+		println!("\nfailed:\n{}", format_word_grid(&failures));
+	}
 
 	Ok(())
 }
@@ -53,6 +640,103 @@ fn format_word_grid(words: &[&str]) -> String {
 		.join("\n")
 }
 
+fn format_suggestions(suggestions: &[Suggestion]) -> String {
+	suggestions
+		.iter()
+		.map(|s| {
+			format!(
+				"\t{}\t{}\t{}{}",
+				s.word.bright_yellow(),
+				format!("{:.2} bits", s.entropy).dimmed(),
+				format!("~{:.1} left", s.expected_remaining).dimmed(),
+				if s.is_candidate {
+					"\tpossible answer".blue().to_string()
+				} else {
+					String::new()
+				}
+			)
+		})
+		.collect::<Vec<_>>()
+		.join("\n")
+}
+
+/// Width of a row result and its remaining count, for aligning solved boards
+fn result_width(tokenized: &[Option<Vec<GuessResultToken>>]) -> usize {
+	tokenized
+		.iter()
+		.flatten()
+		.map(Vec::len)
+		.max()
+		.unwrap_or_default()
+		.saturating_add(6)
+}
+
+/// Register a guess result. If it contradicts the guess results before it,
+/// print them all with the entries that disagree marked.
+fn register_or_explain(
+	notwordle: &mut Notwordle,
+	result: &str,
+) -> Result<Vec<GuessResultToken>, NotwordleError> {
+	let contradiction = match notwordle.register_guess_result(result) {
+		Err(NotwordleError::Contradiction(contradiction)) => contradiction,
+		registered => return registered,
+	};
+
+	if let Ok(tokenized) = tokenize_guess_result(result) {
+		let rows = notwordle.guesses().chain([tokenized.as_slice()]);
+
+		eprintln!("{}", format_contradiction(rows, &contradiction));
+	}
+
+	Err(contradiction.into())
+}
+
+/// Numbered guess results, with the entries a contradiction points at in red
+/// and marked with `^` underneath
+fn format_contradiction<'a>(
+	rows: impl Iterator<Item = &'a [GuessResultToken]>,
+	contradiction: &Contradiction,
+) -> String {
+	let tiles = contradiction.tiles();
+
+	rows.enumerate()
+		.map(|(row, tokens)| {
+			let label = format!("{}. ", row.saturating_add(1));
+			let is_marked = |position| tiles.contains(&GuessTile { row, position });
+			let letters = tokens
+				.iter()
+				.enumerate()
+				.map(|(position, token)| {
+					let (GuessResultToken::Right(c)
+					| GuessResultToken::WrongPosition(c)
+					| GuessResultToken::Wrong(c)) = token;
+
+					if is_marked(position) {
+						c.red().bold().to_string()
+					} else {
+						format_notwordle_guess_result(slice::from_ref(token))
+					}
+				})
+				.collect::<String>();
+			let markers = (0..tokens.len())
+				.map(|position| if is_marked(position) { '^' } else { ' ' })
+				.collect::<String>();
+
+			if markers.trim().is_empty() {
+				format!("{}{letters}", label.dimmed())
+			} else {
+				format!(
+					"{}{letters}\n{}{}",
+					label.dimmed(),
+					" ".repeat(label.len()),
+					markers.trim_end().red()
+				)
+			}
+		})
+		.collect::<Vec<_>>()
+		.join("\n")
+}
+
 fn format_notwordle_guess_result(result: &[GuessResultToken]) -> String {
 	result
 		.iter()
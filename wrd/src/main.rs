@@ -1,118 +1,222 @@
-use std::process;
+use std::io::{self, IsTerminal};
+use std::path::PathBuf;
+use std::{env, process};
 
-use clap::{Parser, Subcommand};
-use wrd::{match_words_runner, notwordle_runner};
+use clap::{Parser, Subcommand, ValueEnum};
+use wrd::{
+	MwArgs, NwArgs, bench_runner, crypt_runner, parse_dictionary_arg, play_runner, repl_runner,
+};
+use wrd_lib::{BenchOptions, Dictionary, DictionaryRegistry, GameOptions, SecretWord, Strategy};
 
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
 struct Cli {
 	#[command(subcommand)]
 	command: Option<Commands>,
+
+	/// dictionary to search: "moby", "gwicks", or a path to a word list
+	///
+	/// a word list is a plain text file with one word per line. a path to a
+	/// directory loads every file in the directory
+	///
+	/// defaults to moby, or for nw --session, the dictionary saved in the
+	/// session
+	///
+	/// e.g.{n}
+	///   wrd -d gwicks mw -p '* r * nt !sy'{n}
+	///   wrd -d ./answers.txt nw -g 'p ?l !a ?t e'
+	#[arg(short, long, global = true)]
+	dictionary: Option<String>,
+
+	/// word frequencies for the dictionary, used by --min-frequency,
+	/// --sort frequency and to favour common answers in suggestions
+	///
+	/// a plain text file with a word and how often it is used on each line,
+	/// e.g. "crane 1204"
+	///
+	/// e.g.{n}
+	///   wrd -f ./counts.txt nw -g 'p ?l !a ?t e' --min-frequency 100
+	#[arg(short, long, global = true, value_name = "PATH")]
+	frequencies: Option<PathBuf>,
 }
 
 #[derive(Subcommand, Debug)]
 enum Commands {
 	/// find word matches from patterns
-	Mw {
-		/// matches lowercase ascii only. character positions seperated by space
-		///
-		/// wrd mw -p '** * ae !bcd **'
-		///
-		/// - **: match any number of any character{n}
-		/// - *: match any single character{n}
-		/// - a-z: match any of these chars (1 or more) in this position{n}
-		/// - !a-z: exclude any of these chars (1 or more) in this position
-		///
-		/// e.g.{n}
-		/// - match any word starting with "y" and "e"{n}
-		///   wrd mw -p 'y e **'
-		///
-		/// - match any word ending with "ry" or "ny"{n}
-		///   wrd mw -p '** rn y'
-		///
-		/// - match a five character word with "r" as 2nd char, "n" or "t"
-		///   as 4th char, and not "s" or "y" as the last character{n}
-		///   wrd mw -p '* r * nt !sy'
-		#[arg(short, long)]
-		pattern: String,
-
-		/// words can only contain characters within this group
-		///
-		/// e.g.{n}
-		/// - find all imperfect anagrams{n}
-		///   wrd mw -p '**' -w 'theword'
-		#[arg(short, long, default_value_t = ("").to_string())]
-		within: String,
-
-		/// word must include all of these characters
-		///
-		/// e.g.{n}
-		/// - find all perfect anagrams{n}
-		///   wrd mw -p '* * * * * *' -i 'miters'
-		///
-		/// - find all permutations with at least 4 characters{n}
-		///   and containing a key character{n}
-		///   wrd mw -p '* * * * **' -i 'd' -w 'abcdefg'
-		#[arg(short, long, default_value_t = ("").to_string())]
-		include: String,
-
-		/// words must not include any of these characters
-		#[arg(short, long, default_value_t = ("").to_string())]
-		exclude: String,
+	Mw(MwArgs),
+
+	/// decode substitution cipher words, e.g. from a cryptogram
+	///
+	/// lists the words each ciphertext word could be, matching the pattern of
+	/// repeated letters. case and punctuation are ignored
+	///
+	/// e.g.{n}
+	///   wrd crypt 'GSZG RH GSV JFVHGRLM'
+	Crypt {
+		/// space seperated ciphertext words
+		ciphertext: String,
 	},
 
 	// see words left after guesses
-	Nw {
-		/// guess results: comma seperated list of guess results
-		///
-		/// a guess result is a space seperated list of the results of a guess:{n}
-		/// - single a-z: letter in correct position{n}
-		/// - ? + single a-z: letter in word but in wrong position{n}
-		/// - ! + single a-z: letter not in word{n}
-		///
-		/// e.g. encoding the result of the guess "plate" where:{n}
-		/// - 'p' is in correct position{n}
-		/// - 'l' is in word but in wrong position{n}
-		/// - 'a' is not in word{n}
-		/// - 't' is in word but in wrong position{n}
-		/// - 'e' is in correct position{n}
-		///
-		/// wrd nw 'p ?l !a ?t e'
-		///
-		/// to see words remaning from compounding guesses, provide a comma
-		/// seperated list of results:
-		///
-		/// wrd nw 'p ?l !a ?t e,p ?o l ?i t'
-		///
+	Nw(NwArgs),
+
+	/// play wordle: guess a secret word from the dictionary
+	///
+	/// type a guess and press enter. feedback uses the same colours as nw:{n}
+	/// - underlined yellow: letter in correct position{n}
+	/// - blue: letter in word but in wrong position{n}
+	/// - dimmed: letter not in word
+	///
+	/// e.g.{n}
+	/// - play today's word with hard mode{n}
+	///   wrd play --daily --hard
+	///
+	/// - play a six letter word with eight guesses{n}
+	///   wrd play -l 6 -m 8
+	Play {
+		/// pick the word from a seed, to replay or share a game
+		#[arg(short, long, conflicts_with = "daily")]
+		seed: Option<u64>,
+
+		/// play the word of the day
+		#[arg(long)]
+		daily: bool,
+
+		/// number of letters in the word
+		#[arg(short, long, default_value_t = 5)]
+		length: usize,
+
+		/// number of guesses allowed
+		#[arg(short, long, default_value_t = 6)]
+		max_guesses: usize,
+
+		/// revealed hints must be used in subsequent guesses
+		#[arg(long)]
+		hard: bool,
+	},
+
+	/// start an interactive session, also started by running wrd alone
+	///
+	/// keeps the dictionary loaded and guess results between commands: add
+	/// guesses one at a time, undo them and run match queries. type help in
+	/// the session for its commands
+	Repl,
+
+	/// measure a solver: solve every word of a length in the dictionary and
+	/// report how many guesses it takes
+	///
+	/// e.g.{n}
+	/// - see how well "crane" opens against the gwicks dictionary{n}
+	///   wrd -d gwicks bench -o crane
+	Bench {
+		/// number of letters in the words
+		#[arg(short, long, default_value_t = 5)]
+		length: usize,
+
+		/// first guess for every game, picked by the strategy if not given
 		#[arg(short, long)]
-		guess_results: String,
+		opening: Option<String>,
+
+		/// how to pick each guess after the opening
+		#[arg(short, long, value_enum, default_value_t = StrategyArg::Entropy)]
+		strategy: StrategyArg,
+
+		/// games needing more guesses than this count as failures
+		#[arg(short, long, default_value_t = 6)]
+		max_guesses: usize,
 	},
 }
 
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum StrategyArg {
+	/// the most informative word that could still be the answer
+	Entropy,
+	/// the most informative word, even if already ruled out (slow)
+	EntropyAny,
+	/// the first word that could still be the answer
+	First,
+}
+
+impl From<StrategyArg> for Strategy {
+	fn from(value: StrategyArg) -> Self {
+		match value {
+			StrategyArg::Entropy => Self::Entropy,
+			StrategyArg::EntropyAny => Self::EntropyAny,
+			StrategyArg::First => Self::First,
+		}
+	}
+}
+
 fn main() {
 	let cli = Cli::parse();
 
-	match &cli.command {
-		Some(Commands::Mw {
-			pattern,
-			exclude,
-			include,
-			within,
-		}) => {
-			if let Err(err) = match_words_runner(pattern, include, exclude, within) {
-				eprintln!("mw error: {err}");
-				process::exit(1);
-			}
-		}
-		Some(Commands::Nw { guess_results }) => {
-			if let Err(err) = notwordle_runner(guess_results) {
+	if env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty()) || !io::stdout().is_terminal()
+	{
+		colored::control::set_override(false);
+	}
+	let dictionary = match (&cli.dictionary, &cli.command) {
+		(Some(name), _) => parse_dictionary_arg(name),
+		(None, Some(Commands::Nw(args))) => match args.session_dictionary() {
+			Ok(saved) => saved.unwrap_or(Dictionary::Moby),
+			Err(err) => {
 				eprintln!("nw error: {err}");
 				process::exit(1);
 			}
+		},
+		(None, _) => Dictionary::Moby,
+	};
+
+	if let Some(path) = &cli.frequencies
+		&& let Err(err) = DictionaryRegistry::global().load_frequencies(&dictionary, path)
+	{
+		eprintln!("frequencies error: {err}");
+		process::exit(1);
+	}
+
+	let (command, result) = match &cli.command {
+		Some(Commands::Mw(args)) => ("mw", args.run(&dictionary)),
+		Some(Commands::Crypt { ciphertext }) => ("crypt", crypt_runner(ciphertext, &dictionary)),
+		Some(Commands::Nw(args)) => ("nw", args.run(&dictionary)),
+		Some(Commands::Play {
+			seed,
+			daily,
+			length,
+			max_guesses,
+			hard,
+		}) => {
+			let secret = match (seed, daily) {
+				(Some(seed), _) => SecretWord::Seed(*seed),
+				(None, true) => SecretWord::today(),
+				(None, false) => SecretWord::random(),
+			};
+			let options = GameOptions {
+				word_length: *length,
+				max_guesses: *max_guesses,
+				hard_mode: *hard,
+			};
+
+			("play", play_runner(secret, options, &dictionary))
 		}
-		None => {
-			eprintln!("expected a command");
-			process::exit(1);
+		Some(Commands::Bench {
+			length,
+			opening,
+			strategy,
+			max_guesses,
+		}) => {
+			let options = BenchOptions {
+				word_length: *length,
+				opening: opening.as_ref().map(|o| o.trim().to_lowercase()),
+				strategy: (*strategy).into(),
+				max_guesses: *max_guesses,
+			};
+
+			("bench", bench_runner(&options, &dictionary))
 		}
+		Some(Commands::Repl) | None => ("repl", repl_runner(dictionary)),
+	};
+
+	if let Err(err) = result {
+		eprintln!("{command} error: {err}");
+		process::exit(1);
 	}
 }
@@ -0,0 +1,219 @@
+use clap::ValueEnum;
+use serde::Serialize;
+use wrd_lib::{GuessResultToken, Suggestion};
+
+use crate::format_word_grid;
+
+/// How to print results
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+	/// columns of words, for reading
+	#[default]
+	Grid,
+	/// one word per line
+	Lines,
+	/// comma separated values with a header row
+	Csv,
+	/// a json object
+	Json,
+}
+
+/// A list of words in `format`
+pub fn format_words(words: &[&str], format: OutputFormat) -> String {
+	match format {
+		OutputFormat::Grid => format_word_grid(words),
+		OutputFormat::Lines => words.join("\n"),
+		OutputFormat::Csv => csv(&["word"], words.iter().map(|word| vec![word.to_string()])),
+		OutputFormat::Json => json(&WordList {
+			count: words.len(),
+			words,
+		}),
+	}
+}
+
+/// A number of words in `format`
+pub fn format_count(count: usize, format: OutputFormat) -> String {
+	match format {
+		OutputFormat::Grid | OutputFormat::Lines => count.to_string(),
+		OutputFormat::Csv => csv(&["count"], [vec![count.to_string()]]),
+		OutputFormat::Json => json(&Count { count }),
+	}
+}
+
+fn csv(header: &[&str], rows: impl IntoIterator<Item = Vec<String>>) -> String {
+	let field = |value: &str| {
+		if value.contains([',', '"', '\n']) {
+			format!("\"{}\"", value.replace('"', "\"\""))
+		} else {
+			value.to_string()
+		}
+	};
+	let header = header
+		.iter()
+		.map(|h| field(h))
+		.collect::<Vec<_>>()
+		.join(",");
+
+	rows.into_iter()
+		.map(|row| row.iter().map(|v| field(v)).collect::<Vec<_>>().join(","))
+		.fold(header, |csv, row| format!("{csv}\n{row}"))
+}
+
+fn json(value: &impl Serialize) -> String {
+	// only fails for maps with non-string keys, which are never output
+	serde_json::to_string_pretty(value).unwrap_or_default()
+}
+
+#[derive(Serialize)]
+struct WordList<'a> {
+	count: usize,
+	words: &'a [&'a str],
+}
+
+#[derive(Serialize)]
+struct Count {
+	count: usize,
+}
+
+/// A guess result and the words left after it, for json output
+#[derive(Serialize)]
+pub struct GuessRow {
+	result: String,
+	letters: Vec<GuessLetter>,
+	remaining: usize,
+}
+
+impl GuessRow {
+	pub fn new(tokens: &[GuessResultToken], remaining: usize) -> Self {
+		Self {
+			result: tokens
+				.iter()
+				.map(ToString::to_string)
+				.collect::<Vec<_>>()
+				.join(" "),
+			letters: tokens.iter().map(GuessLetter::from).collect(),
+			remaining,
+		}
+	}
+}
+
+#[derive(Serialize)]
+struct GuessLetter {
+	letter: String,
+	status: &'static str,
+}
+
+impl From<&GuessResultToken> for GuessLetter {
+	fn from(token: &GuessResultToken) -> Self {
+		let (letter, status) = match token {
+			GuessResultToken::Right(c) => (c, "right"),
+			GuessResultToken::WrongPosition(c) => (c, "wrong_position"),
+			GuessResultToken::Wrong(c) => (c, "wrong"),
+		};
+
+		Self {
+			letter: letter.clone(),
+			status,
+		}
+	}
+}
+
+#[derive(Serialize)]
+struct SuggestionRow {
+	word: &'static str,
+	entropy: f64,
+	expected_remaining: f64,
+	is_candidate: bool,
+}
+
+impl From<&Suggestion> for SuggestionRow {
+	fn from(suggestion: &Suggestion) -> Self {
+		Self {
+			word: suggestion.word,
+			entropy: suggestion.entropy,
+			expected_remaining: suggestion.expected_remaining,
+			is_candidate: suggestion.is_candidate,
+		}
+	}
+}
+
+/// Everything `wrd nw` works out, as json
+pub fn format_notwordle_json(
+	guesses: Vec<GuessRow>,
+	remaining: &[&str],
+	suggestions: Option<&[Suggestion]>,
+) -> String {
+	#[derive(Serialize)]
+	struct Report<'a> {
+		guesses: Vec<GuessRow>,
+		count: usize,
+		remaining: &'a [&'a str],
+		#[serde(skip_serializing_if = "Option::is_none")]
+		suggestions: Option<Vec<SuggestionRow>>,
+	}
+
+	json(&Report {
+		guesses,
+		count: remaining.len(),
+		remaining,
+		suggestions: suggestions.map(|s| s.iter().map(SuggestionRow::from).collect()),
+	})
+}
+
+/// Like `format_notwordle_json` for multi-board variants. Each guess has a row
+/// per board, `None` for boards already solved.
+pub fn format_multi_notwordle_json(
+	guesses: Vec<Vec<Option<GuessRow>>>,
+	boards: &[(Vec<&str>, bool)],
+	suggestions: Option<&[Suggestion]>,
+) -> String {
+	#[derive(Serialize)]
+	struct Board<'a> {
+		solved: bool,
+		count: usize,
+		remaining: &'a [&'a str],
+	}
+
+	#[derive(Serialize)]
+	struct Report<'a> {
+		guesses: Vec<Vec<Option<GuessRow>>>,
+		boards: Vec<Board<'a>>,
+		#[serde(skip_serializing_if = "Option::is_none")]
+		suggestions: Option<Vec<SuggestionRow>>,
+	}
+
+	json(&Report {
+		guesses,
+		boards: boards
+			.iter()
+			.map(|(remaining, solved)| Board {
+				solved: *solved,
+				count: remaining.len(),
+				remaining,
+			})
+			.collect(),
+		suggestions: suggestions.map(|s| s.iter().map(SuggestionRow::from).collect()),
+	})
+}
+
+/// The words left on each board in `format`, for formats other than json
+pub fn format_boards(boards: &[(Vec<&str>, bool)], format: OutputFormat) -> String {
+	match format {
+		OutputFormat::Csv => csv(
+			&["board", "word"],
+			boards
+				.iter()
+				.enumerate()
+				.flat_map(|(index, (remaining, _))| {
+					remaining.iter().map(move |word| {
+						vec![index.saturating_add(1).to_string(), word.to_string()]
+					})
+				}),
+		),
+		_ => boards
+			.iter()
+			.map(|(remaining, _)| format_words(remaining, format))
+			.collect::<Vec<_>>()
+			.join("\n\n"),
+	}
+}
@@ -0,0 +1,300 @@
+use std::env;
+use std::error::Error;
+use std::path::PathBuf;
+
+use clap::{Command, CommandFactory, Parser};
+use colored::Colorize;
+use rustyline::completion::Completer;
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::DefaultHistory;
+use rustyline::validate::Validator;
+use rustyline::{Context, Editor, Helper};
+use wrd_lib::{Dictionary, DictionaryRegistry, GuessPool, Notwordle, get_dictionary};
+
+use crate::{
+	MwArgs, NwArgs, format_notwordle_guess_result, format_suggestions, format_word_grid,
+	parse_dictionary_arg, register_or_explain,
+};
+
+/// Commands in a `wrd repl` session
+#[derive(Parser, Debug)]
+#[command(multicall = true)]
+enum ReplCommand {
+	/// add a guess result and see the words left
+	///
+	/// e.g.{n}
+	///   guess p ?l !a ?t e{n}
+	///   g plate:gy.yg
+	#[command(visible_alias = "g")]
+	Guess {
+		/// a guess result, in any format `wrd nw` takes
+		#[arg(required = true, num_args = 1.., allow_hyphen_values = true)]
+		result: Vec<String>,
+	},
+
+	/// remove the last guess result
+	#[command(visible_alias = "u")]
+	Undo,
+
+	/// remove every guess result
+	Clear,
+
+	/// show the guess results and the words left
+	#[command(visible_alias = "s")]
+	Show,
+
+	/// suggest the next guess from the words left
+	Suggest {
+		/// number of guesses to suggest
+		#[arg(default_value_t = 10)]
+		count: usize,
+
+		/// suggest from any dictionary word of the right length
+		#[arg(short, long)]
+		any: bool,
+	},
+
+	/// find word matches from patterns, as `wrd mw` does
+	Mw(MwArgs),
+
+	/// see words left after guesses, as `wrd nw` does. the session's guesses
+	/// are left alone
+	Nw(NwArgs),
+
+	/// show the dictionary in use, or switch to another one
+	///
+	/// e.g.{n}
+	///   dict gwicks{n}
+	///   dict ./answers.txt
+	Dict {
+		/// "moby", "gwicks", or a path to a word list
+		dictionary: Option<String>,
+	},
+
+	/// leave the session
+	#[command(visible_alias = "exit")]
+	Quit,
+}
+
+/// The dictionary and guess results of a session
+struct Session {
+	dictionary: Dictionary,
+	notwordle: Notwordle,
+}
+
+impl Session {
+	/// Run a command. Returns `false` once the session is over.
+	fn run(&mut self, command: ReplCommand) -> Result<bool, Box<dyn Error>> {
+		match command {
+			ReplCommand::Guess { result } => {
+				let tokenized = register_or_explain(&mut self.notwordle, &result.join(" "))?;
+				let remaining = self.remaining()?;
+
+				println!(
+					"{} remaining after {}",
+					remaining.len(),
+					format_notwordle_guess_result(&tokenized)
+				);
+				println!("{}", format_word_grid(&remaining));
+			}
+			ReplCommand::Undo => {
+				let last = self.notwordle.guesses().len().checked_sub(1);
+
+				if let Some(last) = last {
+					self.notwordle.remove_guess(last)?;
+					self.show()?;
+				} else {
+					println!("no guesses to undo");
+				}
+			}
+			ReplCommand::Clear => self.notwordle.clear(),
+			ReplCommand::Show => self.show()?,
+			ReplCommand::Suggest { count, any } => {
+				let pool = if any {
+					GuessPool::Dictionary
+				} else {
+					GuessPool::Remaining
+				};
+				let dict = get_dictionary(&self.dictionary)?;
+				let frequencies = DictionaryRegistry::global().frequencies(&self.dictionary);
+				let suggestions =
+					self.notwordle
+						.suggest(Some(dict), frequencies.as_deref(), pool, count)?;
+
+				println!("{}", format_suggestions(&suggestions));
+			}
+			ReplCommand::Mw(args) => args.run(&self.dictionary)?,
+			ReplCommand::Nw(args) => args.run(&self.dictionary)?,
+			ReplCommand::Dict { dictionary } => {
+				if let Some(name) = dictionary {
+					let dictionary = parse_dictionary_arg(&name);
+
+					get_dictionary(&dictionary)?;
+					self.dictionary = dictionary;
+				}
+
+				println!(
+					"using {} ({} words)",
+					self.dictionary.name().bright_yellow(),
+					get_dictionary(&self.dictionary)?.len()
+				);
+			}
+			ReplCommand::Quit => return Ok(false),
+		}
+
+		Ok(true)
+	}
+
+	fn remaining(&self) -> Result<Vec<&str>, Box<dyn Error>> {
+		let dict = get_dictionary(&self.dictionary)?;
+
+		Ok(self.notwordle.refine(Some(dict))?)
+	}
+
+	fn show(&self) -> Result<(), Box<dyn Error>> {
+		if self.notwordle.guesses().len() == 0 {
+			println!("no guesses yet, add one with: guess p ?l !a ?t e");
+			return Ok(());
+		}
+
+		for (index, tokens) in self.notwordle.guesses().enumerate() {
+			println!(
+				"{} {}",
+				format!("{}.", index.saturating_add(1)).dimmed(),
+				format_notwordle_guess_result(tokens)
+			);
+		}
+
+		let remaining = self.remaining()?;
+
+		println!("{} remaining", remaining.len());
+		println!("{}", format_word_grid(&remaining));
+
+		Ok(())
+	}
+}
+
+/// Completes command names, then dictionary names for `dict` and flags for
+/// everything else
+struct ReplHelper;
+
+impl Completer for ReplHelper {
+	type Candidate = String;
+
+	fn complete(
+		&self,
+		line: &str,
+		pos: usize,
+		_: &Context<'_>,
+	) -> rustyline::Result<(usize, Vec<String>)> {
+		let before = line.get(..pos).unwrap_or_default();
+		let start = before.rfind(' ').map_or(0, |space| space.saturating_add(1));
+		let word = before.get(start..).unwrap_or_default();
+		let command = ReplCommand::command();
+		let candidates: Vec<String> = match before.split_whitespace().next() {
+			_ if start == 0 => command
+				.get_subcommands()
+				.flat_map(|sub| sub.get_name_and_visible_aliases())
+				.map(str::to_string)
+				.collect(),
+			Some("dict") => DictionaryRegistry::global()
+				.dictionaries()
+				.iter()
+				.map(|dict| dict.name().to_string())
+				.collect(),
+			Some(name) if word.starts_with('-') => command
+				.find_subcommand(name)
+				.into_iter()
+				.flat_map(Command::get_arguments)
+				.filter_map(|arg| arg.get_long())
+				.map(|long| format!("--{long}"))
+				.collect(),
+			_ => vec![],
+		};
+
+		Ok((
+			start,
+			candidates
+				.into_iter()
+				.filter(|candidate| candidate.starts_with(word))
+				.collect(),
+		))
+	}
+}
+
+impl Hinter for ReplHelper {
+	type Hint = String;
+}
+
+impl Highlighter for ReplHelper {}
+
+impl Validator for ReplHelper {}
+
+impl Helper for ReplHelper {}
+
+/// Run an interactive session: add and undo guess results, run match queries
+/// and switch dictionaries, with the dictionary and guesses kept between
+/// commands. History is kept in `~/.wrd_history`.
+///
+/// # Errors
+/// Propagates errors from `get_dictionary` and the line editor.
+pub fn repl_runner(dictionary: Dictionary) -> Result<(), Box<dyn Error>> {
+	let words = get_dictionary(&dictionary)?.len();
+	let mut editor: Editor<ReplHelper, DefaultHistory> = Editor::new()?;
+	let history = env::var_os("HOME").map(|home| PathBuf::from(home).join(".wrd_history"));
+	let mut session = Session {
+		dictionary,
+		notwordle: Notwordle::default(),
+	};
+
+	editor.set_helper(Some(ReplHelper));
+
+	if let Some(history) = &history {
+		// there is no history the first time
+		let _ = editor.load_history(history);
+	}
+
+	println!(
+		"using {} ({words} words). type help for commands, tab to complete",
+		session.dictionary.name().bright_yellow()
+	);
+
+	loop {
+		let line = match editor.readline("wrd> ") {
+			Ok(line) => line,
+			Err(ReadlineError::Interrupted) => continue,
+			Err(ReadlineError::Eof) => break,
+			Err(err) => return Err(err.into()),
+		};
+
+		if line.trim().is_empty() {
+			continue;
+		}
+
+		editor.add_history_entry(line.as_str())?;
+
+		let Some(args) = shlex::split(&line) else {
+			println!("{}", "error: unclosed quote".red());
+			continue;
+		};
+
+		match ReplCommand::try_parse_from(args) {
+			Ok(command) => match session.run(command) {
+				Ok(true) => {}
+				Ok(false) => break,
+				Err(err) => println!("{}", format!("error: {err}").red()),
+			},
+			Err(err) => {
+				let _ = err.print();
+			}
+		}
+	}
+
+	if let Some(history) = &history {
+		editor.save_history(history)?;
+	}
+
+	Ok(())
+}
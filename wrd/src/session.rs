@@ -0,0 +1,35 @@
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+use wrd_lib::{Dictionary, Notwordle, format_guess_result};
+
+/// A saved session, as `wrd-tui` writes it: the dictionary in use and the Not
+/// wordle guess results. Anything else in the file is left alone
+#[derive(Deserialize, Debug)]
+pub struct Session {
+	pub dictionary: Option<Dictionary>,
+	#[serde(flatten)]
+	pub notwordle: Notwordle,
+}
+
+impl Session {
+	/// Read a session file.
+	///
+	/// # Errors
+	/// Errors if the file can not be read, is not a session, or its guess
+	/// results are invalid.
+	pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+		let json = fs::read_to_string(path)
+			.map_err(|err| format!("could not read session {}: {err}", path.display()))?;
+
+		serde_json::from_str(&json)
+			.map_err(|err| format!("invalid session {}: {err}", path.display()).into())
+	}
+
+	/// The guess results as rows of entries, e.g. `p ?l !a t !e`
+	pub fn guess_results(&self) -> impl Iterator<Item = String> {
+		self.notwordle.guesses().map(format_guess_result)
+	}
+}